@@ -125,6 +125,26 @@ fn message(In(args): In<Args>) -> Action<Args, bool> {
     }).with(args)
 }
 
+/// Shows the message dialog with an `Ok` button, and blocks until it is closed.
+///
+/// This is the same dialog as [`DialogMessagePlugin`] opens.
+pub fn show_message_dialog(message: impl Into<String>) {
+    ask_system(Args {
+        question_message: message.into(),
+        ..Default::default()
+    }, MessageButtons::Ok);
+}
+
+/// Shows the dialog to confirm ok/cancel with the user, and returns `true` if `Ok` is selected.
+///
+/// This is the same dialog as [`DialogConfirmPlugin`] opens.
+pub fn show_confirm_dialog(message: impl Into<String>) -> bool {
+    ask_system(Args {
+        question_message: message.into(),
+        ..Default::default()
+    }, MessageButtons::OkCancel)
+}
+
 fn ask_system(
     args: Args,
     buttons: MessageButtons,
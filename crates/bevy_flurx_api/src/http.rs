@@ -4,11 +4,12 @@ use crate::error::http::DenyOrigin;
 use crate::error::ApiResult;
 use crate::macros::api_plugin;
 use bevy::app::PluginGroupBuilder;
-use bevy::prelude::{In, PluginGroup, Reflect, ReflectDefault, ReflectDeserialize, ReflectResource, ReflectSerialize, Res, Resource, Update};
+use bevy::prelude::{Entity, In, PluginGroup, Query, Reflect, ReflectDefault, ReflectDeserialize, ReflectResource, ReflectSerialize, Res, Resource, Update};
 use bevy::utils::HashMap;
 use bevy_flurx::prelude::{once, side_effect, Pipe};
 use bevy_flurx::task::ReactorTask;
 use bevy_flurx_ipc::prelude::*;
+use bevy_webview_core::prelude::WebviewProxy;
 use reqwest::header::{HeaderMap, HeaderName};
 use reqwest::{Method, Response};
 use serde::{Deserialize, Serialize};
@@ -28,6 +29,8 @@ impl PluginGroup for AllHttpPlugins {
 api_plugin!(
     /// You'll be able to use fetch bevy_flurx_api.
     ///
+    /// If the webview has [`WebviewProxy`], requests are sent through the proxy server.
+    ///
     /// ## Typescript Code Example
     ///
     /// ```ts
@@ -43,11 +46,17 @@ struct Args {
     body: Option<Vec<u8>>,
     method: Option<String>,
     headers: Option<HashMap<String, String>>,
+    #[serde(skip)]
+    proxy: Option<String>,
 }
 
 impl Args {
     async fn fetch(self) -> ApiResult<Response> {
-        let mut client = reqwest::Client::new().request(self.method()?, &self.url);
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = self.proxy.as_ref() {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        let mut client = builder.build()?.request(self.method()?, &self.url);
         if let Some(headers) = self.headers {
             client = client.headers(to_header_map(&headers)?);
         }
@@ -111,10 +120,11 @@ impl AccessAllowOrigins {
 }
 
 #[command(id = "FLURX|http::fetch")]
-async fn fetch(In(args): In<Args>, task: ReactorTask) -> ApiResult<Output> {
+async fn fetch(In(args): In<Args>, WebviewEntity(entity): WebviewEntity, task: ReactorTask) -> ApiResult<Output> {
     task.will(
         Update,
-        once::run(error_if_deny_access).with(args)
+        once::run(apply_webview_proxy).with((args, entity))
+            .pipe(once::run(error_if_deny_access))
             .pipe(side_effect::tokio::spawn(|args: ApiResult<Args>| async move {
                 let args = args?;
                 let response = args.fetch().await?;
@@ -130,6 +140,14 @@ async fn fetch(In(args): In<Args>, task: ReactorTask) -> ApiResult<Output> {
         .await
 }
 
+fn apply_webview_proxy(
+    In((mut args, entity)): In<(Args, Entity)>,
+    proxies: Query<&WebviewProxy>,
+) -> Args {
+    args.proxy = proxies.get(entity).ok().map(WebviewProxy::url);
+    args
+}
+
 fn error_if_deny_access(
     In(args): In<Args>,
     allow_origins: Option<Res<AccessAllowOrigins>>,
@@ -164,12 +182,13 @@ fn to_hash_map(headers: &HeaderMap) -> HashMap<String, String> {
 #[cfg(test)]
 mod tests {
     use crate::error::ApiResult;
-    use crate::http::{error_if_deny_access, AccessAllowOrigins, Args};
+    use crate::http::{apply_webview_proxy, error_if_deny_access, AccessAllowOrigins, Args};
     use crate::tests::test_app;
     use bevy::app::{Startup, Update};
     use bevy::prelude::Commands;
     use bevy::utils::default;
     use bevy_flurx::prelude::*;
+    use bevy_webview_core::prelude::WebviewProxy;
     use reqwest::Method;
 
     #[test]
@@ -235,6 +254,22 @@ mod tests {
         assert!(origins.is_allow("https://hoge.com/index.html"));
     }
 
+    #[test]
+    fn apply_proxy_of_webview() {
+        let mut app = test_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            let webview_entity = commands.spawn(WebviewProxy::http("localhost", 8080)).id();
+            let other_entity = commands.spawn_empty().id();
+            commands.spawn(Reactor::schedule(move |task| async move {
+                let args = task.will(Update, once::run(apply_webview_proxy).with((Args::default(), webview_entity))).await;
+                assert_eq!(args.proxy.as_deref(), Some("http://localhost:8080"));
+                let args = task.will(Update, once::run(apply_webview_proxy).with((Args::default(), other_entity))).await;
+                assert_eq!(args.proxy, None);
+            }));
+        });
+        app.update();
+    }
+
     #[test]
     fn output_ok_if_not_exists_allow_origins() {
         let mut app = test_app();
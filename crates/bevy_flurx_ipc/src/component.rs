@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 /// The ipc invoke handlers.
 #[repr(transparent)]
-#[derive(Component, Default)]
+#[derive(Component, Default, Clone)]
 pub struct IpcHandlers(pub(crate) HashMap<String, IpcHandler>);
 
 impl IpcHandlers {
@@ -68,6 +68,7 @@ pub type IpcFn = fn(commands: &mut Commands, IpcCommand);
 /// The ipc invoke handler.
 ///
 /// Usually created via [`command`](bevy_flurx_ipc_macro::command).
+#[derive(Clone)]
 pub struct IpcHandler {
     id: String,
     f: IpcFn,
@@ -1,4 +1,4 @@
-use crate::prelude::{AutoPlay, Background, Bounds, DragDropEvent, DragEntered, DragLeave, DragOver, Dropped, EmbedWithin, EnableClipboard, EventEmitter, GripZone, HotkeysZoom, Incognito, InitializeFocused, IsOpenDevtools, PassedUrl, Resizable, Theme, UseDevtools, UseHttpsScheme, WebviewUri, WebviewVisible};
+use crate::prelude::{AutoPlay, Background, Bounds, Csp, CspDirective, CspOverride, DragDropEvent, DragEntered, DragLeave, DragOver, Dropped, EmbedWithin, EnableClipboard, EventEmitter, FallbackPage, GripZone, HotkeysZoom, Incognito, InheritOpenerConfig, InitializeFocused, IsOpenDevtools, NavigationAction, NavigationPolicy, NavigationRule, PassedUrl, ReloadOnCrash, Resizable, Theme, UrlMatcher, UrlPattern, UseDevtools, UseHttpsScheme, WebviewProfile, WebviewProxy, WebviewUri, WebviewVisible, WebviewZoom};
 use bevy::prelude::{App, Component, Plugin, Reflect, ReflectComponent, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
 
@@ -41,8 +41,22 @@ impl Plugin for WebViewBundlesPlugin {
             .register_type::<Theme>()
             .register_type::<InitializeFocused>()
             .register_type::<HotkeysZoom>()
+            .register_type::<WebviewZoom>()
             .register_type::<Incognito>()
+            .register_type::<WebviewProfile>()
+            .register_type::<WebviewProxy>()
             .register_type::<UseHttpsScheme>()
+            .register_type::<ReloadOnCrash>()
+            .register_type::<FallbackPage>()
+            .register_type::<Csp>()
+            .register_type::<CspDirective>()
+            .register_type::<CspOverride>()
+            .register_type::<InheritOpenerConfig>()
+            .register_type::<NavigationPolicy>()
+            .register_type::<NavigationRule>()
+            .register_type::<NavigationAction>()
+            .register_type::<UrlPattern>()
+            .register_type::<UrlMatcher>()
             .register_type::<PassedUrl>()
             .register_type::<DragDropEvent>()
             .register_type::<DragEntered>()
@@ -5,38 +5,52 @@ pub use background::Background;
 use bevy::prelude::Bundle;
 use bevy_flurx_ipc::component::IpcHandlers;
 pub use browser_accelerator_keys::BrowserAcceleratorKeys;
-pub use csp::Csp;
+pub use csp::{Csp, CspDirective, CspOverride};
 pub use enable_clipboard::EnableClipboard;
 pub use event_emitter::EventEmitter;
+pub use fallback_page::FallbackPage;
 pub use focused::InitializeFocused;
 pub use handler::*;
 pub use hotkeys_zoom::HotkeysZoom;
 pub use https_scheme::UseHttpsScheme;
 pub use incognito::Incognito;
+pub use inherit_opener_config::InheritOpenerConfig;
 pub use initialization_script::InitializationScripts;
 pub use is_open_devtools::IsOpenDevtools;
+pub use navigation_policy::*;
+pub use profile::WebviewProfile;
+pub use proxy::WebviewProxy;
+pub use reload_on_crash::ReloadOnCrash;
 pub use theme::Theme;
 pub use use_devtools::UseDevtools;
 pub use user_agent::UserAgent;
 pub use visible::WebviewVisible;
 pub use webview_uri::*;
+pub use zoom::WebviewZoom;
 
 mod auto_play;
 mod background;
 mod browser_accelerator_keys;
 mod enable_clipboard;
 mod event_emitter;
+mod fallback_page;
 mod focused;
 mod handler;
 mod hotkeys_zoom;
 mod https_scheme;
 mod incognito;
+mod inherit_opener_config;
 mod is_open_devtools;
+mod navigation_policy;
+mod profile;
+mod proxy;
+mod reload_on_crash;
 mod theme;
 mod use_devtools;
 mod user_agent;
 mod visible;
 mod webview_uri;
+mod zoom;
 mod csp;
 mod initialization_script;
 
@@ -84,9 +98,15 @@ pub struct WebViewBundle {
     /// [`HotkeysZoom`]
     pub hotkeys_zoom: HotkeysZoom,
 
+    /// [`WebviewZoom`]
+    pub zoom: WebviewZoom,
+
     /// [`UseHttpsScheme`]
     pub use_https_scheme: UseHttpsScheme,
 
+    /// [`ReloadOnCrash`]
+    pub reload_on_crash: ReloadOnCrash,
+
     /// [`IpcHandlers`]
     pub ipc_handlers: IpcHandlers,
 
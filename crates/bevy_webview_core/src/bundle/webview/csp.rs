@@ -1,15 +1,287 @@
-//! Apply [Content Security Policy](https://developer.mozilla.org/ja/docs/Web/HTTP/Headers/Content-Security-Policy) for the webview. 
+//! Apply [Content Security Policy](https://developer.mozilla.org/ja/docs/Web/HTTP/Headers/Content-Security-Policy) for the webview.
 
 use bevy::prelude::{Component, Reflect, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectSerialize};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 /// Represents the [Content Security Policy](https://developer.mozilla.org/ja/docs/Web/HTTP/Headers/Content-Security-Policy).
+///
+/// The policy is sent as the `Content-Security-Policy` header of the resources served from the `flurx` protocol,
+/// and is inserted as the `<meta>` tag into [`Webview::Html`](crate::prelude::Webview::Html).
+///
+/// If [`Csp::nonce`] is enabled, a random nonce is generated for each HTML document served from the `flurx` protocol
+/// or given by [`Webview::Html`](crate::prelude::Webview::Html), and is added to `script-src` and `style-src`.
+/// The document can read it from `window.__FLURX__.cspNonce` once it starts parsing;
+/// it is not exposed to pages of other origins, and such documents are not cached.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy_webview_core::prelude::*;
+///
+/// Csp::default()
+///     .default_src(["'self'"])
+///     .script_src(["'self'"])
+///     .img_src(["'self'", "data:"])
+///     .nonce(true)
+///     .override_path("editor/**", Csp::default().script_src(["'self'", "'unsafe-eval'"]));
+///
+/// // Also can be parsed from the policy string.
+/// Csp::from("default-src 'self'; img-src 'self' data:");
+/// ```
 #[derive(Component, Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
 #[reflect(Component, Default, Serialize, Deserialize)]
-pub struct Csp(pub String);
+pub struct Csp {
+    directives: Vec<CspDirective>,
+    nonce: bool,
+    overrides: Vec<CspOverride>,
+}
+
+/// Represents the single directive of [`Csp`] such as `script-src 'self'`.
+#[derive(Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
+pub struct CspDirective {
+    /// The directive name such as `script-src`.
+    pub name: String,
+
+    /// The source expressions such as `'self'`.
+    pub sources: Vec<String>,
+}
+
+/// The directives applied to the resources whose paths match [`CspOverride::path`].
+#[derive(Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
+pub struct CspOverride {
+    /// The glob pattern of the path relative to `flurx://localhost/`.
+    ///
+    /// `*` matches any characters except `/`, and `**` matches any characters including `/`.
+    pub path: String,
+
+    /// The directives which replace the directives of the same name.
+    pub directives: Vec<CspDirective>,
+}
+
+impl Csp {
+    /// Sets the sources of the directive `name`.
+    ///
+    /// If the directive already exists, its sources are replaced.
+    pub fn directive<S: Into<String>>(mut self, name: impl Into<String>, sources: impl IntoIterator<Item=S>) -> Self {
+        set_directive(&mut self.directives, CspDirective {
+            name: name.into(),
+            sources: sources.into_iter().map(S::into).collect(),
+        });
+        self
+    }
+
+    /// Sets `default-src`.
+    #[inline]
+    pub fn default_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("default-src", sources)
+    }
+
+    /// Sets `script-src`.
+    #[inline]
+    pub fn script_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("script-src", sources)
+    }
+
+    /// Sets `style-src`.
+    #[inline]
+    pub fn style_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("style-src", sources)
+    }
+
+    /// Sets `img-src`.
+    #[inline]
+    pub fn img_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("img-src", sources)
+    }
+
+    /// Sets `connect-src`.
+    #[inline]
+    pub fn connect_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("connect-src", sources)
+    }
+
+    /// Sets `font-src`.
+    #[inline]
+    pub fn font_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("font-src", sources)
+    }
+
+    /// Sets `media-src`.
+    #[inline]
+    pub fn media_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("media-src", sources)
+    }
+
+    /// Sets `frame-src`.
+    #[inline]
+    pub fn frame_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("frame-src", sources)
+    }
+
+    /// Sets `worker-src`.
+    #[inline]
+    pub fn worker_src<S: Into<String>>(self, sources: impl IntoIterator<Item=S>) -> Self {
+        self.directive("worker-src", sources)
+    }
+
+    /// Specifies whether to generate the nonce for each document.
+    pub fn nonce(mut self, enable: bool) -> Self {
+        self.nonce = enable;
+        self
+    }
+
+    /// Replaces the directives for the resources whose paths match the glob pattern `path`.
+    ///
+    /// Directives not specified in `csp` are inherited from this policy.
+    /// If multiple patterns match, the one added first is used.
+    pub fn override_path(mut self, path: impl Into<String>, csp: Csp) -> Self {
+        self.overrides.push(CspOverride {
+            path: path.into(),
+            directives: csp.directives,
+        });
+        self
+    }
+
+    /// Returns the directives.
+    #[inline]
+    pub fn directives(&self) -> &[CspDirective] {
+        &self.directives
+    }
+
+    /// Returns whether the nonce is generated.
+    #[inline]
+    pub const fn uses_nonce(&self) -> bool {
+        self.nonce
+    }
+
+    /// Returns the per-path overrides.
+    #[inline]
+    pub fn overrides(&self) -> &[CspOverride] {
+        &self.overrides
+    }
+
+    /// Returns the policy in which `overridden` directives replace the directives of the same name.
+    pub fn with_directives(&self, overridden: &[CspDirective]) -> Csp {
+        let mut csp = self.clone();
+        for directive in overridden {
+            set_directive(&mut csp.directives, directive.clone());
+        }
+        csp
+    }
+
+    /// Converts into the policy string.
+    ///
+    /// If `nonce` is specified, it is added to `script-src` and `style-src`.
+    /// If these directives don't exist, they are created from `default-src`.
+    pub fn to_policy(&self, nonce: Option<&str>) -> String {
+        let Some(nonce) = nonce else {
+            return self.to_string();
+        };
+        let default_src = self
+            .directives
+            .iter()
+            .find(|directive| directive.name == "default-src");
+        let mut csp = self.clone();
+        for name in ["script-src", "style-src"] {
+            let source = format!("'nonce-{nonce}'");
+            match csp.directives.iter_mut().find(|directive| directive.name == name) {
+                Some(directive) => directive.sources.push(source),
+                None => {
+                    let mut sources = default_src.map(|d| d.sources.clone()).unwrap_or_default();
+                    sources.push(source);
+                    csp.directives.push(CspDirective {
+                        name: name.to_string(),
+                        sources,
+                    });
+                }
+            }
+        }
+        csp.to_string()
+    }
+}
+
+fn set_directive(directives: &mut Vec<CspDirective>, directive: CspDirective) {
+    match directives.iter_mut().find(|d| d.name == directive.name) {
+        Some(d) => d.sources = directive.sources,
+        None => directives.push(directive),
+    }
+}
+
+impl Display for Csp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let policy = self
+            .directives
+            .iter()
+            .map(|directive| {
+                if directive.sources.is_empty() {
+                    directive.name.clone()
+                } else {
+                    format!("{} {}", directive.name, directive.sources.join(" "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        f.write_str(&policy)
+    }
+}
 
 impl From<&str> for Csp {
     fn from(value: &str) -> Self {
-        Csp(value.to_string())
+        value
+            .split(';')
+            .filter_map(|directive| {
+                let mut tokens = directive.split_whitespace();
+                Some((tokens.next()?, tokens))
+            })
+            .fold(Csp::default(), |csp, (name, sources)| csp.directive(name, sources))
+    }
+}
+
+impl From<String> for Csp {
+    #[inline]
+    fn from(value: String) -> Self {
+        Csp::from(value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Csp;
+
+    #[test]
+    fn parse_policy() {
+        let csp = Csp::from("default-src 'self';  img-src 'self' data: ; upgrade-insecure-requests");
+        assert_eq!(csp.to_string(), "default-src 'self'; img-src 'self' data:; upgrade-insecure-requests");
+    }
+
+    #[test]
+    fn replace_existing_directive() {
+        let csp = Csp::default()
+            .default_src(["'self'"])
+            .script_src(["'self'"])
+            .script_src(["'none'"]);
+        assert_eq!(csp.to_string(), "default-src 'self'; script-src 'none'");
+    }
+
+    #[test]
+    fn add_nonce() {
+        let csp = Csp::default().default_src(["'self'"]).script_src(["'self'"]);
+        assert_eq!(
+            csp.to_policy(Some("abc")),
+            "default-src 'self'; script-src 'self' 'nonce-abc'; style-src 'self' 'nonce-abc'",
+        );
+    }
+
+    #[test]
+    fn override_directives() {
+        let csp = Csp::default()
+            .default_src(["'self'"])
+            .script_src(["'self'"])
+            .override_path("editor/**", Csp::default().script_src(["'self'", "'unsafe-eval'"]));
+        let overridden = csp.with_directives(&csp.overrides()[0].directives);
+        assert_eq!(overridden.to_string(), "default-src 'self'; script-src 'self' 'unsafe-eval'");
     }
-}
\ No newline at end of file
+}
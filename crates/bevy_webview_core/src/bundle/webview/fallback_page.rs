@@ -0,0 +1,33 @@
+use crate::prelude::WebviewUri;
+use bevy::prelude::{Component, ReflectComponent, ReflectDeserialize, ReflectSerialize};
+use bevy::prelude::Reflect;
+use serde::{Deserialize, Serialize};
+
+/// The page shown instead of the error page when the webview fails to load a remote page,
+/// for example, while offline.
+///
+/// Failures of local pages don't show the fallback page.
+///
+/// ## Platform-specific
+///
+/// - **Windows / macOS / Android / iOS**: Not supported; the platform's error page is shown.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy_webview_wry::prelude::*;
+///
+/// (
+///     Webview::Uri(WebviewUri::new("https://example.com/news")),
+///     FallbackPage::Uri(WebviewUri::relative_local("offline.html")),
+/// );
+/// ```
+#[derive(Component, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Serialize, Deserialize)]
+pub enum FallbackPage {
+    /// Load the specified uri, which is usually a local resource.
+    Uri(WebviewUri),
+
+    /// Load the specified html content.
+    Html(String),
+}
@@ -8,15 +8,29 @@ pub(crate) type BoxedDownloadHandler = Box<dyn FnMut(PassedUrl, &mut PathBuf) ->
 /// The callback handler for download requests.
 ///
 /// If the return value of the callback is `false`, the download is canceled.
-#[repr(transparent)]
 #[derive(Component, Default)]
-pub struct OnDownload(Option<BoxedDownloadHandler>);
+pub struct OnDownload {
+    handler: Option<BoxedDownloadHandler>,
+    deferred: bool,
+}
 
 impl OnDownload {
     /// No callback is specified.
     ///
     /// All downloads are allowed and the download destination is not changed.
-    pub const NONE: Self = Self(None);
+    pub const NONE: Self = Self {
+        handler: None,
+        deferred: false,
+    };
+
+    /// Decides downloads in systems.
+    ///
+    /// The download is canceled once and `NavigationRequested` is fired;
+    /// if a system allows it, the download is restarted to the default destination.
+    pub const DEFERRED: Self = Self {
+        handler: None,
+        deferred: true,
+    };
 
     /// Creates the new [`OnDownload`].
     ///
@@ -27,13 +41,22 @@ impl OnDownload {
     ///
     /// The callback returns a `bool` to allow or deny the download.
     pub fn new(f: impl FnMut(PassedUrl, &mut PathBuf) -> bool + Send + Sync + 'static) -> Self {
-        Self(Some(Box::new(f)))
+        Self {
+            handler: Some(Box::new(f)),
+            deferred: false,
+        }
+    }
+
+    /// Returns whether downloads are decided in systems.
+    #[inline]
+    pub const fn is_deferred(&self) -> bool {
+        self.deferred
     }
 
     /// Take the callback.
     #[inline]
     pub fn take(&mut self) -> Option<BoxedDownloadHandler> {
-        self.0.take()
+        self.handler.take()
     }
 }
 
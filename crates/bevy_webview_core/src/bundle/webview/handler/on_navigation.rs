@@ -5,26 +5,50 @@ pub(crate) type BoxedNavigateHandler = Box<dyn Fn(PassedUrl) -> bool + Send + Sy
 
 
 /// Set a navigation handler to decide if incoming url is allowed to navigate.
-#[repr(transparent)]
 #[derive(Component, Default)]
-pub struct OnNavigation(Option<BoxedNavigateHandler>);
+pub struct OnNavigation {
+    handler: Option<BoxedNavigateHandler>,
+    deferred: bool,
+}
 
 impl OnNavigation {
     /// No callback is specified.
     ///
     /// All navigation is permitted.
-    pub const NONE: Self = Self(None);
+    pub const NONE: Self = Self {
+        handler: None,
+        deferred: false,
+    };
+
+    /// Decides navigations in systems.
+    ///
+    /// The navigation is canceled once and `NavigationRequested` is fired;
+    /// if a system allows it, the webview navigates to the url again.
+    /// The initial url of the webview is allowed without the event.
+    pub const DEFERRED: Self = Self {
+        handler: None,
+        deferred: true,
+    };
 
     /// Creates the new [`OnNavigation`].
     ///
     /// If the return value of the callback is `false`, navigation is canceled.
     pub fn new(f: impl Fn(PassedUrl) -> bool + Send + Sync + 'static) -> Self {
-        Self(Some(Box::new(f)))
+        Self {
+            handler: Some(Box::new(f)),
+            deferred: false,
+        }
+    }
+
+    /// Returns whether navigations are decided in systems.
+    #[inline]
+    pub const fn is_deferred(&self) -> bool {
+        self.deferred
     }
 
     /// Take the callback.
     pub fn take(&mut self) -> Option<BoxedNavigateHandler> {
-        self.0.take()
+        self.handler.take()
     }
 }
 
@@ -1,5 +1,6 @@
 use super::PassedUrl;
-use bevy::prelude::{Component, Window};
+use crate::prelude::Bounds;
+use bevy::prelude::{Component, Entity, Window};
 
 pub(crate) type BoxedNewWindowRequest = Box<dyn Fn(PassedUrl) -> NewWindowResponse + Send + Sync + 'static>;
 
@@ -9,6 +10,18 @@ pub(crate) type BoxedNewWindowRequest = Box<dyn Fn(PassedUrl) -> NewWindowRespon
 pub enum NewWindowResponse {
     /// Create new window and open url in it.
     CreateWindow(Window),
+    /// Create new child window of the opener's window and open url in it.
+    ///
+    /// This requires the `child_window` feature of `bevy_webview_wry`;
+    /// otherwise, the window is created as a top-level window.
+    CreateChildWindow(Window),
+    /// Create new webview embedded in `parent` window and open url in it.
+    CreateEmbedded {
+        /// The window entity to embed the webview in.
+        parent: Entity,
+        /// The bounds of the webview.
+        bounds: Bounds,
+    },
     /// Allow open url in current window.
     Allow,
     /// Deny open url.
@@ -16,20 +29,40 @@ pub enum NewWindowResponse {
 }
 
 /// Set a new window request handler to decide if incoming url is allowed to be opened.
-#[repr(transparent)]
 #[derive(Component, Default)]
-pub struct OnNewWindowRequest(Option<BoxedNewWindowRequest>);
+pub struct OnNewWindowRequest {
+    handler: Option<BoxedNewWindowRequest>,
+    deferred: bool,
+}
 
 impl OnNewWindowRequest {
+    /// Decides new window requests in systems.
+    ///
+    /// The request is denied once and `NavigationRequested` is fired;
+    /// if a system allows it, the url is opened in the current window.
+    pub const DEFERRED: Self = Self {
+        handler: None,
+        deferred: true,
+    };
+
     /// Creates the [`OnNewWindowRequest`].
     pub fn new(f: impl Fn(PassedUrl) -> NewWindowResponse + Send + Sync + 'static) -> Self {
-        Self(Some(Box::new(f)))
+        Self {
+            handler: Some(Box::new(f)),
+            deferred: false,
+        }
+    }
+
+    /// Returns whether new window requests are decided in systems.
+    #[inline]
+    pub const fn is_deferred(&self) -> bool {
+        self.deferred
     }
 
     /// Take the callback.
     #[inline]
     pub fn take(&mut self) -> Option<BoxedNewWindowRequest> {
-        self.0.take()
+        self.handler.take()
     }
 }
 
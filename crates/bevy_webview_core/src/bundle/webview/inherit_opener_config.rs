@@ -0,0 +1,14 @@
+use bevy::prelude::{Component, Reflect, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Whether the webviews opened by [`NewWindowResponse`](crate::prelude::NewWindowResponse) inherit
+/// the configuration components and `IpcHandlers` of the opener.
+///
+/// The inherited components include `UserAgent`, `Csp`, `InitializationScripts`, `NavigationPolicy` and this component itself,
+/// but not the callback handlers such as [`OnNavigation`](crate::prelude::OnNavigation).
+///
+/// Default is `false`.
+#[repr(transparent)]
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Hash, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct InheritOpenerConfig(pub bool);
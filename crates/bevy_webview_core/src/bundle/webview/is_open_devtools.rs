@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## Notes
 ///
-/// * __This only works in debug builds.__
+/// * __This only works in debug builds, or in release builds with the `devtools` feature of `bevy_webview_wry`.__
 /// * Opening and closing devtools doesn't seem to be supported on some platforms; 
 #[repr(transparent)]
 #[derive(Component, Clone, Debug, Eq, PartialEq, Hash, Default, Reflect, Serialize, Deserialize)]
@@ -0,0 +1,185 @@
+use bevy::prelude::{Component, Reflect, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Decides whether the webview is allowed to navigate to the url with the ordered rules.
+///
+/// The first rule whose [`UrlPattern`] matches the url is applied,
+/// and [`NavigationPolicy::default_action`] is applied if no rule matches.
+///
+/// Unlike [`OnNavigation`](crate::prelude::OnNavigation), this can be serialized and changed at runtime.
+/// If both are specified, [`OnNavigation`](crate::prelude::OnNavigation) is called only when this policy allows the navigation.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy_webview_core::prelude::*;
+///
+/// NavigationPolicy::default()
+///     .allow(UrlPattern::default().scheme("flurx"))
+///     .open_externally(UrlPattern::default().scheme("https").host("*.example.com"))
+///     .deny(UrlPattern::default().path(UrlMatcher::regex(r"^store(/|$)")))
+///     .with_default_action(NavigationAction::Deny);
+/// ```
+#[derive(Component, Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct NavigationPolicy {
+    /// The rules evaluated in order.
+    pub rules: Vec<NavigationRule>,
+
+    /// The action applied if no rule matches.
+    ///
+    /// Default is [`NavigationAction::Allow`].
+    pub default_action: NavigationAction,
+}
+
+/// The rule of [`NavigationPolicy`].
+#[derive(Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
+pub struct NavigationRule {
+    /// The action applied if [`NavigationRule::url`] matches.
+    pub action: NavigationAction,
+
+    /// The pattern of the url.
+    pub url: UrlPattern,
+}
+
+/// The action applied to the navigation.
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
+pub enum NavigationAction {
+    /// Allows the navigation.
+    #[default]
+    Allow,
+
+    /// Cancels the navigation.
+    Deny,
+
+    /// Cancels the navigation and opens the url in the system browser.
+    ///
+    /// Only `http`, `https` and `mailto` urls are opened; the others are just cancelled.
+    OpenExternally,
+}
+
+/// Represents the pattern of the url.
+///
+/// The url matches if all specified parts match.
+/// Schemes and hosts are compared in lowercase, and paths are compared without the leading `/`,
+/// for example, `store/items` for `https://example.com/store/items?id=1`.
+#[derive(Default, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Serialize, Deserialize)]
+pub struct UrlPattern {
+    /// The pattern of the scheme such as `https`.
+    ///
+    /// If `None`, matches any scheme.
+    pub scheme: Option<UrlMatcher>,
+
+    /// The pattern of the host such as `*.example.com`.
+    ///
+    /// If `None`, matches any host.
+    pub host: Option<UrlMatcher>,
+
+    /// The pattern of the path such as `store/**`.
+    ///
+    /// If `None`, matches any path.
+    pub path: Option<UrlMatcher>,
+}
+
+/// Represents how to match a part of the url.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Serialize, Deserialize)]
+pub enum UrlMatcher {
+    /// The glob pattern.
+    ///
+    /// `*` does not match `/`, while `**` matches any characters including `/`.
+    Glob(String),
+
+    /// The regular expression.
+    ///
+    /// This is not anchored, so use `^` and `$` to match the whole part.
+    ///
+    /// If the expression is invalid, the rule is ignored, except that a deny rule denies all urls.
+    Regex(String),
+}
+
+impl NavigationPolicy {
+    /// Appends the rule which allows the navigation to the url matching `url`.
+    #[inline]
+    pub fn allow(self, url: UrlPattern) -> Self {
+        self.rule(NavigationAction::Allow, url)
+    }
+
+    /// Appends the rule which cancels the navigation to the url matching `url`.
+    #[inline]
+    pub fn deny(self, url: UrlPattern) -> Self {
+        self.rule(NavigationAction::Deny, url)
+    }
+
+    /// Appends the rule which opens the url matching `url` in the system browser.
+    #[inline]
+    pub fn open_externally(self, url: UrlPattern) -> Self {
+        self.rule(NavigationAction::OpenExternally, url)
+    }
+
+    /// Appends the rule.
+    pub fn rule(mut self, action: NavigationAction, url: UrlPattern) -> Self {
+        self.rules.push(NavigationRule {
+            action,
+            url,
+        });
+        self
+    }
+
+    /// Sets the action applied if no rule matches.
+    pub fn with_default_action(mut self, action: NavigationAction) -> Self {
+        self.default_action = action;
+        self
+    }
+}
+
+impl UrlPattern {
+    /// Sets the pattern of the scheme.
+    pub fn scheme(mut self, scheme: impl Into<UrlMatcher>) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Sets the pattern of the host.
+    pub fn host(mut self, host: impl Into<UrlMatcher>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Sets the pattern of the path.
+    pub fn path(mut self, path: impl Into<UrlMatcher>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl UrlMatcher {
+    /// Creates [`UrlMatcher::Glob`].
+    #[inline]
+    pub fn glob(pattern: impl Into<String>) -> Self {
+        Self::Glob(pattern.into())
+    }
+
+    /// Creates [`UrlMatcher::Regex`].
+    #[inline]
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        Self::Regex(pattern.into())
+    }
+}
+
+impl From<&str> for UrlMatcher {
+    #[inline]
+    fn from(pattern: &str) -> Self {
+        Self::Glob(pattern.to_string())
+    }
+}
+
+impl From<String> for UrlMatcher {
+    #[inline]
+    fn from(pattern: String) -> Self {
+        Self::Glob(pattern)
+    }
+}
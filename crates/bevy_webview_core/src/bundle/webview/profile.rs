@@ -0,0 +1,24 @@
+use bevy::prelude::{Component, Reflect, ReflectComponent, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The name of the profile in which the webview stores its browsing data such as cookies and local storage.
+///
+/// Webviews with the same profile share the browsing data, and it persists between sessions
+/// in a directory named after the profile.
+/// Webviews without this component use the platform's default data directory.
+///
+/// If [`Incognito`](crate::prelude::Incognito) is `true`, this component is ignored.
+///
+/// On Linux, download handlers are registered per profile, so download events of a webview
+/// may also be reported for the other webviews in the same profile.
+#[repr(transparent)]
+#[derive(Component, Debug, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Serialize, Deserialize)]
+pub struct WebviewProfile(pub String);
+
+impl WebviewProfile {
+    /// Creates the new [`WebviewProfile`].
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::{Component, Reflect, ReflectComponent, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The proxy server through which the webview connects.
+///
+/// This is applied when the webview is created, and also to `bevy_flurx_api::http::fetch` requests made by the webview.
+///
+/// ## Platform-specific
+///
+/// - **Linux**: The proxy is set on the shared `WebContext`, so webviews sharing a [`WebviewProfile`](crate::prelude::WebviewProfile) use the proxy of the one created last.
+/// - **macOS**: Requires macOS 14.0+.
+/// - **Android / iOS**: Not supported.
+#[derive(Component, Debug, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Serialize, Deserialize)]
+pub enum WebviewProxy {
+    /// Connects via HTTP CONNECT.
+    Http {
+        /// The host of the proxy server such as `localhost` or `192.168.0.100`.
+        host: String,
+        /// The port of the proxy server.
+        port: u16,
+    },
+
+    /// Connects via SOCKS5.
+    Socks5 {
+        /// The host of the proxy server such as `localhost` or `192.168.0.100`.
+        host: String,
+        /// The port of the proxy server.
+        port: u16,
+    },
+}
+
+impl WebviewProxy {
+    /// Creates the new [`WebviewProxy::Http`].
+    pub fn http(host: impl Into<String>, port: u16) -> Self {
+        Self::Http {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Creates the new [`WebviewProxy::Socks5`].
+    pub fn socks5(host: impl Into<String>, port: u16) -> Self {
+        Self::Socks5 {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Returns the host of the proxy server.
+    pub fn host(&self) -> &str {
+        match self {
+            Self::Http { host, .. } | Self::Socks5 { host, .. } => host,
+        }
+    }
+
+    /// Returns the port of the proxy server.
+    pub fn port(&self) -> u16 {
+        match self {
+            Self::Http { port, .. } | Self::Socks5 { port, .. } => *port,
+        }
+    }
+
+    /// Returns the url of the proxy server such as `http://localhost:8080` or `socks5://[::1]:1080`.
+    pub fn url(&self) -> String {
+        let scheme = match self {
+            Self::Http { .. } => "http",
+            Self::Socks5 { .. } => "socks5",
+        };
+        let host = self.host();
+        if host.contains(':') && !host.starts_with('[') {
+            format!("{scheme}://[{host}]:{}", self.port())
+        } else {
+            format!("{scheme}://{host}:{}", self.port())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::WebviewProxy;
+
+    #[test]
+    fn http_url() {
+        assert_eq!(WebviewProxy::http("localhost", 8080).url(), "http://localhost:8080");
+    }
+
+    #[test]
+    fn socks5_url_with_ipv6() {
+        assert_eq!(WebviewProxy::socks5("::1", 1080).url(), "socks5://[::1]:1080");
+        assert_eq!(WebviewProxy::socks5("[::1]", 1080).url(), "socks5://[::1]:1080");
+    }
+}
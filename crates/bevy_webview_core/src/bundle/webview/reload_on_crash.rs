@@ -0,0 +1,13 @@
+use bevy::prelude::{Component, ReflectComponent, ReflectDeserialize, ReflectSerialize};
+use bevy::prelude::{Reflect, ReflectDefault};
+use serde::{Deserialize, Serialize};
+
+/// Represents whether to reload the page automatically when the web content process crashes.
+///
+/// Default is `false`.
+///
+/// This setting only works on `Linux`.
+#[repr(transparent)]
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Hash, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct ReloadOnCrash(pub bool);
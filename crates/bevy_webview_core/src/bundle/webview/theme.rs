@@ -6,7 +6,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// Default is [`Theme::Auto`].
 ///
-/// This setting only works on `Windows`.
+/// [`Theme::Auto`] follows [`Window::window_theme`](bevy::window::Window::window_theme) if it is set,
+/// otherwise the system theme. Changes are applied while the webview is running.
+///
+/// The resolved theme can be read from `window.__FLURX__.theme` on all platforms,
+/// but `prefers-color-scheme` of the page follows it only on `Windows`.
 #[derive(Default, Component, Copy, Clone, Eq, PartialEq, Reflect, Serialize, Deserialize)]
 #[reflect(Component, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
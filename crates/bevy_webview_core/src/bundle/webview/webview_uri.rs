@@ -18,7 +18,8 @@ use std::path::{Path, PathBuf};
 ///         DefaultPlugins,
 ///         WebviewWryPlugin{
 ///             // local root will be flurx://localhost/ui. 
-///             local_root: PathBuf::from("ui")
+///             local_root: LocalRoot::Assets(PathBuf::from("ui")),
+///             ..default()
 ///         }
 ///     ))
 ///     .run();
@@ -46,7 +47,9 @@ use std::path::{Path, PathBuf};
     InitializationScripts,
     Incognito,
     HotkeysZoom,
+    WebviewZoom,
     UseHttpsScheme,
+    ReloadOnCrash,
     IpcHandlers,
     OnDownload,
     OnDragDrop,
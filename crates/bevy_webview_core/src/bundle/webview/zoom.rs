@@ -0,0 +1,23 @@
+use bevy::prelude::{Component, ReflectComponent, ReflectDeserialize, ReflectSerialize};
+use bevy::prelude::{Reflect, ReflectDefault};
+use serde::{Deserialize, Serialize};
+
+/// The zoom factor of the page.
+///
+/// The zoom is multiplied by the ratio of the window's scale factor to the scale factor of the monitor,
+/// so overriding the scale factor of the window, for example, by `WindowResolution::set_scale_factor_override`,
+/// also scales the webviews in it.
+///
+/// This is applied whenever it changes, and updated when the user zooms with hotkeys or gestures.
+///
+/// Default is `1.0`.
+#[repr(transparent)]
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub struct WebviewZoom(pub f64);
+
+impl Default for WebviewZoom {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
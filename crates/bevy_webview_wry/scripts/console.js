@@ -0,0 +1,58 @@
+(() => {
+    const post = (payload) => window.__FLURX__.emit("FLURX|console", payload);
+    const format = (args) => args.map((arg) => {
+        if (typeof arg === "string") {
+            return arg;
+        }
+        if (arg instanceof Error) {
+            return arg.stack ?? String(arg);
+        }
+        try {
+            return JSON.stringify(arg) ?? String(arg);
+        } catch {
+            return String(arg);
+        }
+    }).join(" ");
+    const levels = {
+        trace: "trace",
+        debug: "debug",
+        log: "info",
+        info: "info",
+        warn: "warn",
+        error: "error",
+    };
+    for (const [method, level] of Object.entries(levels)) {
+        const original = console[method].bind(console);
+        // The name marks the frame of this wrapper, so the caller is found in `callerStack`.
+        console[method] = function __flurxConsole(...args) {
+            original(...args);
+            try {
+                const error = args.find((arg) => arg instanceof Error);
+                post({
+                    level,
+                    kind: level === "error" ? "consoleError" : null,
+                    message: format(args),
+                    stack: error?.stack ?? null,
+                    callerStack: new Error().stack ?? null,
+                });
+            } catch {
+                // The console must not throw even if the message can't be forwarded.
+            }
+        };
+    }
+    window.addEventListener("error", (e) => post({
+        level: "error",
+        kind: "uncaught",
+        message: e.message,
+        source: e.filename || null,
+        line: e.lineno || null,
+        column: e.colno || null,
+        stack: e.error?.stack ?? null,
+    }));
+    window.addEventListener("unhandledrejection", (e) => post({
+        level: "error",
+        kind: "unhandledRejection",
+        message: e.reason instanceof Error ? e.reason.message : format([e.reason]),
+        stack: e.reason?.stack ?? null,
+    }));
+})();
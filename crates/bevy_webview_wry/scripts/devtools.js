@@ -0,0 +1,18 @@
+(() => {
+    const shortcut = __SHORTCUT__;
+    window.addEventListener("keydown", (e) => {
+        if (
+            e.code !== shortcut.code ||
+            e.ctrlKey !== shortcut.ctrl ||
+            e.shiftKey !== shortcut.shift ||
+            e.altKey !== shortcut.alt ||
+            e.metaKey !== shortcut.meta ||
+            e.repeat
+        ) {
+            return;
+        }
+        e.preventDefault();
+        e.stopPropagation();
+        window.__FLURX__.emit("FLURX|devtools::toggle", {});
+    }, true);
+})();
@@ -0,0 +1,28 @@
+(() => {
+    const request = (kind, message, defaultValue = null) => new Promise((resolve) => {
+        const resolveId = window.crypto.getRandomValues(new Uint32Array(1))[0];
+        const resolveKey = `_${resolveId}`;
+        Object.defineProperty(window.__FLURX__, resolveKey, {
+            value: (output) => {
+                Reflect.deleteProperty(window.__FLURX__, resolveKey);
+                resolve(output);
+            },
+            writable: false,
+            configurable: true,
+        });
+        window.__FLURX__.emit("FLURX|dialog", {
+            resolveId,
+            kind,
+            message: message === undefined ? "" : String(message),
+            defaultValue: defaultValue === null || defaultValue === undefined ? null : String(defaultValue),
+        });
+    });
+    window.alert = async (message) => {
+        await request("alert", message);
+    };
+    window.confirm = async (message) => Boolean(await request("confirm", message));
+    window.prompt = async (message, defaultValue) => {
+        const text = await request("prompt", message, defaultValue);
+        return typeof text === "string" ? text : null;
+    };
+})();
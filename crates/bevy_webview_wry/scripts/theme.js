@@ -0,0 +1,21 @@
+(() => {
+    let theme = null;
+    Object.defineProperty(window.__FLURX__, "theme", {
+        get: () => theme,
+        configurable: true,
+    });
+    Object.defineProperty(window.__FLURX__, "__setTheme", {
+        value: (newTheme) => {
+            if (theme === newTheme) {
+                return;
+            }
+            theme = newTheme;
+            window.dispatchEvent(new CustomEvent("flurx:themechange", {
+                detail: {theme},
+            }));
+        },
+        writable: false,
+        configurable: true,
+    });
+    window.__FLURX__.emit("FLURX|theme", {});
+})();
@@ -0,0 +1,14 @@
+(() => {
+    const post = () => window.__FLURX__.emit("FLURX|zoom", {
+        devicePixelRatio: window.devicePixelRatio,
+    });
+    const watch = () => {
+        window
+            .matchMedia(`(resolution: ${window.devicePixelRatio}dppx)`)
+            .addEventListener("change", () => {
+                post();
+                watch();
+            }, {once: true});
+    };
+    watch();
+})();
@@ -1,6 +1,12 @@
 //! Provides the minimum functionality required to display webview.
 
 use crate::embedding::EmbeddingWebviewPlugin;
+use crate::prelude::{DevtoolsShortcut, LocalRoot, ProtocolAccess, ProtocolHeaders};
+use crate::webview::devtools::DevtoolsPlugin;
+use crate::webview::local_root::WebviewMounts;
+use crate::webview::profile::WryWebContexts;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::io::AssetSourceId;
 use bevy::prelude::*;
 use bevy_webview_core::bundle::WebViewBundlesPlugin;
 use serde::{Deserialize, Serialize};
@@ -42,22 +48,85 @@ pub mod prelude {
 #[repr(transparent)]
 #[derive(Resource, Debug, Reflect, Clone, Serialize, Deserialize)]
 #[reflect(Resource, Serialize, Deserialize)]
-pub(crate) struct WryLocalRoot(pub PathBuf);
+pub(crate) struct WryLocalRoot(pub LocalRoot);
+
+#[repr(transparent)]
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct WryAssetSource(pub AssetSourceId<'static>);
+
+/// The directory on the disk corresponding to the root of [`WryAssetSource`].
+///
+/// This is `None` if local resources are not read from the file system.
+#[repr(transparent)]
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct WryAssetDir(pub Option<PathBuf>);
 
 /// Provides a mechanism for drawing a webview
 /// in a [`Window`] using [`wry`].
 pub struct WebviewWryPlugin {
-    /// Represents the root directory of the local resource.
+    /// Represents the source of the local resource.
     /// This value affects [`WebviewUri`](prelude::WebviewUri).
     ///
-    /// This directory must be located under the `assets` directory.
-    pub local_root: PathBuf,
+    /// [`LocalRoot::Assets`] must be located under the root of [`WebviewWryPlugin::asset_source`].
+    pub local_root: LocalRoot,
+
+    /// The asset source from which local resources are read.
+    ///
+    /// Local resources are resolved through [`AssetServer`], so [`AssetPlugin::file_path`],
+    /// custom asset sources and embedded assets are respected.
+    ///
+    /// Default is [`AssetSourceId::Default`], which usually refers to the `assets` directory.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::asset::io::AssetSourceId;
+    /// use bevy_webview_wry::prelude::*;
+    /// use std::path::PathBuf;
+    ///
+    /// // Serves files registered by `embedded_asset!(app, "ui/index.html")` in `my_crate`.
+    /// WebviewWryPlugin {
+    ///     local_root: LocalRoot::Assets(PathBuf::from("my_crate").join("ui")),
+    ///     asset_source: AssetSourceId::from("embedded"),
+    ///     ..Default::default()
+    /// };
+    /// ```
+    pub asset_source: AssetSourceId<'static>,
+
+    /// Controls which local resources can be served.
+    ///
+    /// By default, all files except dotfiles under the local root are served.
+    pub protocol_access: ProtocolAccess,
+
+    /// Headers added to the responses of the `flurx` protocol, such as COOP/COEP and CORS headers.
+    ///
+    /// By default, no headers are added.
+    pub protocol_headers: ProtocolHeaders,
+
+    /// The directory in which the data directories of [`WebviewProfile`](prelude::WebviewProfile) are created.
+    ///
+    /// If `None`, `<data_dir>/<executable name>/webview_profiles` is used,
+    /// where `data_dir` is, for example, `~/.local/share` on Linux.
+    pub profiles_dir: Option<PathBuf>,
+
+    /// The shortcut that toggles the devtools of the focused webview.
+    ///
+    /// Devtools are available in debug builds, or in release builds with the `devtools` feature;
+    /// otherwise, this has no effect.
+    ///
+    /// Default is `None`.
+    pub devtools_shortcut: Option<DevtoolsShortcut>,
 }
 
 impl Default for WebviewWryPlugin {
     fn default() -> Self {
         Self {
-            local_root: PathBuf::from("ui"),
+            local_root: LocalRoot::default(),
+            asset_source: AssetSourceId::Default,
+            protocol_access: ProtocolAccess::default(),
+            protocol_headers: ProtocolHeaders::default(),
+            profiles_dir: None,
+            devtools_shortcut: None,
         }
     }
 }
@@ -66,10 +135,20 @@ impl Plugin for WebviewWryPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<WryLocalRoot>()
+            .register_type::<LocalRoot>()
             .insert_resource(WryLocalRoot(self.local_root.clone()))
+            .insert_resource(WryAssetSource(self.asset_source.clone()))
+            .init_resource::<WryAssetDir>()
+            .init_resource::<WebviewMounts>()
+            .insert_resource(self.protocol_access.clone())
+            .insert_resource(self.protocol_headers.clone())
+            .insert_non_send_resource(WryWebContexts::new(self.profiles_dir.clone()))
             .add_plugins((
                 WebviewPlugin,
                 EmbeddingWebviewPlugin,
+                DevtoolsPlugin {
+                    shortcut: self.devtools_shortcut.clone(),
+                },
             ));
 
         if !app.is_plugin_added::<WebViewBundlesPlugin>() {
@@ -84,4 +163,13 @@ impl Plugin for WebviewWryPlugin {
             }
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        let asset_dir = match app.get_added_plugins::<AssetPlugin>().first() {
+            Some(_) if self.asset_source != AssetSourceId::Default => None,
+            Some(asset_plugin) => Some(FileAssetReader::get_base_path().join(&asset_plugin.file_path)),
+            None => Some(PathBuf::from("assets")),
+        };
+        app.insert_resource(WryAssetDir(asset_dir));
+    }
 }
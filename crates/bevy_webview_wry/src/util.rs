@@ -20,4 +20,62 @@ pub(crate) fn as_wry_rect(bounds: &Bounds) -> wry::Rect {
         position: wry::dpi::LogicalPosition::new(bounds.position.x, bounds.position.y).into(),
         size: wry::dpi::LogicalSize::new(bounds.size.x, bounds.size.y).into(),
     }
-}
\ No newline at end of file
+}
+
+/// Returns whether `text` matches the glob `pattern`.
+///
+/// `*` and `?` do not match `/`, while `**` matches any characters including `/`.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[char], text: &[char]) -> bool {
+        match pattern {
+            [] => text.is_empty(),
+            ['*', '*', rest @ ..] => {
+                // `**/` also matches zero directories.
+                (rest.first() == Some(&'/') && matches(&rest[1..], text))
+                    || (0..=text.len()).any(|i| matches(rest, &text[i..]))
+            }
+            ['*', rest @ ..] => {
+                for i in 0..=text.len() {
+                    if matches(rest, &text[i..]) {
+                        return true;
+                    }
+                    if text.get(i) == Some(&'/') {
+                        return false;
+                    }
+                }
+                false
+            }
+            ['?', rest @ ..] => text.first().is_some_and(|c| *c != '/') && matches(rest, &text[1..]),
+            [c, rest @ ..] => text.first() == Some(c) && matches(rest, &text[1..]),
+        }
+    }
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    matches(&pattern, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::glob_match;
+
+    #[test]
+    fn match_single_star() {
+        assert!(glob_match("*.map", "main.js.map"));
+        assert!(!glob_match("*.map", "js/main.js.map"));
+        assert!(glob_match("js/*.js", "js/main.js"));
+    }
+
+    #[test]
+    fn match_double_star() {
+        assert!(glob_match("**/*.map", "main.js.map"));
+        assert!(glob_match("**/*.map", "js/vendor/main.js.map"));
+        assert!(glob_match("private/**", "private/a/b.txt"));
+        assert!(!glob_match("private/**", "public/a.txt"));
+    }
+
+    #[test]
+    fn match_question() {
+        assert!(glob_match("img?.png", "img1.png"));
+        assert!(!glob_match("img?.png", "img/.png"));
+    }
+}
@@ -1,22 +1,45 @@
 //! Provides a mechanism to control the basic behavior of Webview.
 
+use crate::webview::custom_protocol::CustomProtocolPlugin;
 use crate::webview::event_emitter::EventEmitterPlugin;
 use crate::webview::handlers::WryHandlersPlugin;
 use crate::webview::ipc_resolve::IpcResolvePlugin;
+use crate::webview::lifecycle::LifecyclePlugin;
 use crate::webview::load_webview::LoadWebviewPlugin;
+use crate::webview::protocol_access::ProtocolAccessPlugin;
+use crate::webview::protocol_headers::ProtocolHeadersPlugin;
+use crate::webview::snapshot::SnapshotPlugin;
+use crate::webview::theme::ThemePlugin;
 use crate::webview::visible::VisiblePlugin;
+use crate::webview::zoom::ZoomPlugin;
 use bevy::prelude::{App, Deref, DerefMut, Entity, Plugin};
 use bevy::utils::hashbrown::HashMap;
 use bevy_flurx_ipc::FlurxIpcPlugin;
 
+pub mod browsing_data;
+pub mod console;
+pub mod custom_protocol;
+pub mod devtools;
+pub mod dialog;
 mod event_emitter;
 pub mod handlers;
+#[cfg(feature = "hot_reload")]
+pub mod hot_reload;
 mod ipc_resolve;
+pub mod lifecycle;
+pub mod local_root;
 mod load_webview;
+pub(crate) mod profile;
+pub mod protocol_access;
+pub mod protocol_headers;
+#[cfg(feature = "resource_pack")]
+pub mod resource_pack;
+pub mod route;
+mod snapshot;
+mod theme;
 mod visible;
+pub mod zoom;
 
-#[cfg(debug_assertions)]
-mod devtools;
 #[cfg(any(
     target_os = "linux",
     target_os = "dragonfly",
@@ -26,12 +49,30 @@ mod devtools;
 ))]
 mod linux;
 
+pub use snapshot::{snapshot, SnapshotError, SnapshotResult};
+
 #[allow(missing_docs)]
 pub mod prelude {
     pub use crate::webview::{
+        browsing_data::{self, BrowsingDataError, BrowsingDataResult, CookieSameSite, WebviewBrowsingDataPlugin, WebviewCookie},
+        console::{ScriptErrorKind, WebviewConsolePlugin, WebviewScriptError},
+        dialog::{WebviewDialogKind, WebviewDialogPlugin, WebviewDialogRequested, WebviewDialogs},
+        devtools::{DevtoolsClosed, DevtoolsOpened, DevtoolsShortcut},
+        custom_protocol::{IntoProtocolResponse, Json, ProtocolHandler, ProtocolRequest, ProtocolResponse, WebviewProtocolExt},
+        protocol_access::{AccessDeniedReason, ProtocolAccess, ProtocolAccessDenied},
+        protocol_headers::{PathHeaders, ProtocolCors, ProtocolHeader, ProtocolHeaders},
+        route::{Method, RouteHandler, RouteRequest, WebviewRouteExt},
+        snapshot::{snapshot, SnapshotError, SnapshotResult},
         handlers::prelude::*,
+        lifecycle::*,
+        local_root::{LocalRoot, WebviewMountExt},
+        zoom::WebviewZoomChanged,
         WryWebViews,
     };
+    #[cfg(feature = "hot_reload")]
+    pub use crate::webview::hot_reload::{HotReloadKind, WebviewHotReloadPlugin, WebviewReloaded};
+    #[cfg(feature = "resource_pack")]
+    pub use crate::webview::resource_pack::{ResourcePack, ResourcePackBuilder, ResourcePackError, ResourcePackExt};
 }
 
 pub(crate) struct WebviewPlugin;
@@ -49,6 +90,13 @@ impl Plugin for WebviewPlugin {
                 EventEmitterPlugin,
                 IpcResolvePlugin,
                 WryHandlersPlugin,
+                LifecyclePlugin,
+                CustomProtocolPlugin,
+                ProtocolAccessPlugin,
+                ProtocolHeadersPlugin,
+                ZoomPlugin,
+                SnapshotPlugin,
+                ThemePlugin,
                 #[cfg(any(
                     target_os = "linux",
                     target_os = "dragonfly",
@@ -59,9 +107,6 @@ impl Plugin for WebviewPlugin {
                 linux::WebviewSupportLinuxPlugin,
             ))
             .init_non_send_resource::<WryWebViews>();
-
-        #[cfg(debug_assertions)]
-        app.add_plugins(devtools::DevtoolsPlugin);
     }
 }
 
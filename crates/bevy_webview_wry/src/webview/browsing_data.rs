@@ -0,0 +1,443 @@
+//! Provides actions to read, set and delete cookies and to clear the browsing data of webviews.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_flurx::prelude::*;
+//! use bevy_webview_wry::prelude::*;
+//!
+//! fn log_out(mut commands: Commands, webview: Query<Entity, With<Webview>>) {
+//!     let entity = webview.single();
+//!     commands.spawn(Reactor::schedule(move |task| async move {
+//!         if let Err(e) = task.will(Update, browsing_data::clear_browsing_data(entity)).await {
+//!             error!("{e}");
+//!         }
+//!     }));
+//! }
+//! ```
+
+use crate::prelude::WryWebViews;
+use bevy::prelude::{Added, App, Entity, In, NonSend, Plugin, PostUpdate, Query, Reflect};
+use bevy_flurx::action::{once, Action};
+use bevy_flurx_ipc::prelude::*;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+use wry::cookie::{Cookie, SameSite};
+
+/// The result of the actions in [`browsing_data`](crate::webview::browsing_data).
+pub type BrowsingDataResult<T = ()> = Result<T, BrowsingDataError>;
+
+/// Allows you to manage the cookies and the browsing data from a webview.
+///
+/// ## Typescript Code Example
+///
+/// `cookies` returns only the cookies that would be sent to the current page, and the values of `HttpOnly` cookies are empty.
+///
+/// ```ts
+/// const cookies = await window.__FLURX__.browsingData.cookies();
+/// await window.__FLURX__.browsingData.setCookie({ name: "lang", value: "en", path: "/" });
+/// await window.__FLURX__.browsingData.deleteCookie(cookies[0]);
+/// await window.__FLURX__.browsingData.clear();
+/// ```
+pub struct WebviewBrowsingDataPlugin;
+
+impl Plugin for WebviewBrowsingDataPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<WebviewCookie>()
+            .register_type::<CookieSameSite>()
+            .add_systems(PostUpdate, |mut views: Query<&mut IpcHandlers, Added<IpcHandlers>>| {
+                for mut handlers in views.iter_mut() {
+                    handlers.register(cookies_command());
+                    handlers.register(set_cookie_command());
+                    handlers.register(delete_cookie_command());
+                    handlers.register(clear_command());
+                }
+            });
+    }
+}
+
+/// The cookie stored in the webview.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewCookie {
+    /// The name of the cookie.
+    pub name: String,
+
+    /// The value of the cookie.
+    pub value: String,
+
+    /// The `Domain` attribute.
+    #[serde(default)]
+    pub domain: Option<String>,
+
+    /// The `Path` attribute.
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// The `Secure` attribute.
+    #[serde(default)]
+    pub secure: bool,
+
+    /// The `HttpOnly` attribute.
+    ///
+    /// Cookies with this attribute can't be set or deleted by [`set_cookie`] and [`delete_cookie`];
+    /// use [`clear_browsing_data`] to remove them.
+    #[serde(default)]
+    pub http_only: bool,
+
+    /// The `SameSite` attribute.
+    #[serde(default)]
+    pub same_site: Option<CookieSameSite>,
+
+    /// The expiration time in seconds since the unix epoch.
+    ///
+    /// If `None`, the cookie is a session cookie.
+    #[serde(default)]
+    pub expires: Option<i64>,
+}
+
+impl WebviewCookie {
+    /// Creates a new session cookie.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an error if the name, the value or the attributes contain characters
+    /// which can't be used in the cookie, such as `;` which would inject other attributes.
+    fn validate(&self) -> BrowsingDataResult {
+        let is_token = |c: char| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c);
+        let is_cookie_octet = |c: char| c.is_ascii_graphic() && !"\",;\\".contains(c);
+        let is_attribute_value = |c: char| (c.is_ascii_graphic() || c == ' ') && c != ';';
+        let value = self
+            .value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(&self.value);
+        let valid = !self.name.is_empty()
+            && self.name.chars().all(is_token)
+            && value.chars().all(is_cookie_octet)
+            && [self.domain.as_ref(), self.path.as_ref()]
+                .into_iter()
+                .flatten()
+                .all(|attribute| attribute.chars().all(is_attribute_value));
+        if valid {
+            Ok(())
+        } else {
+            Err(BrowsingDataError::InvalidCookie(self.name.clone()))
+        }
+    }
+
+    /// Converts into the `document.cookie` assignment.
+    fn to_document_cookie(&self, now: i64) -> String {
+        let mut cookie = format!("{}={}", self.name, self.value);
+        if let Some(domain) = self.domain.as_ref() {
+            cookie.push_str(&format!("; Domain={domain}"));
+        }
+        if let Some(path) = self.path.as_ref() {
+            cookie.push_str(&format!("; Path={path}"));
+        }
+        if let Some(expires) = self.expires {
+            cookie.push_str(&format!("; Max-Age={}", expires.saturating_sub(now).max(0)));
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        if let Some(same_site) = self.same_site {
+            cookie.push_str(&format!("; SameSite={same_site}"));
+        }
+        cookie
+    }
+}
+
+impl From<&Cookie<'_>> for WebviewCookie {
+    fn from(cookie: &Cookie<'_>) -> Self {
+        Self {
+            name: cookie.name().to_string(),
+            value: cookie.value().to_string(),
+            domain: cookie.domain().map(str::to_string),
+            path: cookie.path().map(str::to_string),
+            secure: cookie.secure().unwrap_or_default(),
+            http_only: cookie.http_only().unwrap_or_default(),
+            same_site: cookie.same_site().map(CookieSameSite::from),
+            expires: cookie.expires_datetime().map(|expires| expires.unix_timestamp()),
+        }
+    }
+}
+
+/// The `SameSite` attribute of [`WebviewCookie`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+pub enum CookieSameSite {
+    /// `SameSite=Strict`
+    Strict,
+
+    /// `SameSite=Lax`
+    Lax,
+
+    /// `SameSite=None`
+    None,
+}
+
+impl Display for CookieSameSite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Strict => f.write_str("Strict"),
+            Self::Lax => f.write_str("Lax"),
+            Self::None => f.write_str("None"),
+        }
+    }
+}
+
+impl From<SameSite> for CookieSameSite {
+    fn from(same_site: SameSite) -> Self {
+        match same_site {
+            SameSite::Strict => Self::Strict,
+            SameSite::Lax => Self::Lax,
+            SameSite::None => Self::None,
+        }
+    }
+}
+
+/// The error that occurs in the actions in [`browsing_data`](crate::webview::browsing_data).
+#[derive(Debug)]
+pub enum BrowsingDataError {
+    /// The webview associated with the entity does not exist.
+    WebviewNotFound(Entity),
+
+    /// `HttpOnly` cookies can't be set or deleted from scripts.
+    HttpOnly(String),
+
+    /// The name, the value or the attributes of the cookie contain invalid characters.
+    InvalidCookie(String),
+
+    /// The webview has no page.
+    NoPage,
+
+    /// The error returned from the webview.
+    Wry(wry::Error),
+}
+
+impl Display for BrowsingDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WebviewNotFound(entity) => write!(f, "webview not found: {entity}"),
+            Self::HttpOnly(name) => write!(f, "HttpOnly cookie can't be modified: {name}"),
+            Self::InvalidCookie(name) => write!(f, "cookie contains invalid characters: {name}"),
+            Self::NoPage => f.write_str("the webview has no page"),
+            Self::Wry(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for BrowsingDataError {}
+
+impl From<wry::Error> for BrowsingDataError {
+    fn from(e: wry::Error) -> Self {
+        Self::Wry(e)
+    }
+}
+
+impl Serialize for BrowsingDataError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Returns all cookies stored in the webview.
+pub fn cookies(entity: Entity) -> Action<(Entity, Option<String>), BrowsingDataResult<Vec<WebviewCookie>>> {
+    once::run(cookies_system).with((entity, None))
+}
+
+/// Returns the cookies that would be sent to `url`.
+pub fn cookies_for_url(
+    entity: Entity,
+    url: impl Into<String>,
+) -> Action<(Entity, Option<String>), BrowsingDataResult<Vec<WebviewCookie>>> {
+    once::run(cookies_system).with((entity, Some(url.into())))
+}
+
+/// Sets the cookie for the current page of the webview.
+///
+/// The cookie is set via `document.cookie`, so it follows the same rules as scripts in the page;
+/// for example, the domain must match the current page, and `HttpOnly` cookies can't be set.
+///
+/// [`BrowsingDataError::InvalidCookie`] is returned if the name, the value or the attributes
+/// contain characters such as `;`.
+/// `Ok` means that the assignment was sent to the page, which may still ignore it;
+/// read the cookie back with [`cookies_for_url`] if you need to confirm it.
+pub fn set_cookie(entity: Entity, cookie: WebviewCookie) -> Action<(Entity, WebviewCookie, bool), BrowsingDataResult> {
+    once::run(write_cookie_system).with((entity, cookie, false))
+}
+
+/// Deletes the cookie matching the name, the domain and the path of `cookie` from the current page of the webview.
+///
+/// Like [`set_cookie`], `HttpOnly` cookies can't be deleted; use [`clear_browsing_data`] instead.
+pub fn delete_cookie(entity: Entity, cookie: WebviewCookie) -> Action<(Entity, WebviewCookie, bool), BrowsingDataResult> {
+    once::run(write_cookie_system).with((entity, cookie, true))
+}
+
+/// Clears all browsing data of the webview, including cookies, caches and local storage.
+///
+/// The data directory may be shared with other webviews, in which case their data is also cleared.
+pub fn clear_browsing_data(entity: Entity) -> Action<Entity, BrowsingDataResult> {
+    once::run(clear_system).with(entity)
+}
+
+fn cookies_system(
+    In((entity, url)): In<(Entity, Option<String>)>,
+    web_views: NonSend<WryWebViews>,
+) -> BrowsingDataResult<Vec<WebviewCookie>> {
+    let webview = web_views.get(&entity).ok_or(BrowsingDataError::WebviewNotFound(entity))?;
+    let cookies = match url {
+        Some(url) => webview.cookies_for_url(&url)?,
+        None => webview.cookies()?,
+    };
+    Ok(cookies.iter().map(WebviewCookie::from).collect())
+}
+
+fn write_cookie_system(
+    In((entity, mut cookie, delete)): In<(Entity, WebviewCookie, bool)>,
+    web_views: NonSend<WryWebViews>,
+) -> BrowsingDataResult {
+    let webview = web_views.get(&entity).ok_or(BrowsingDataError::WebviewNotFound(entity))?;
+    if cookie.http_only {
+        return Err(BrowsingDataError::HttpOnly(cookie.name));
+    }
+    cookie.validate()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs() as i64);
+    if delete {
+        cookie.value.clear();
+        cookie.expires = Some(now);
+    }
+    let document_cookie = serde_json::to_string(&cookie.to_document_cookie(now)).unwrap_or_default();
+    webview.evaluate_script(&format!("document.cookie = {document_cookie};"))?;
+    Ok(())
+}
+
+fn clear_system(
+    In(entity): In<Entity>,
+    web_views: NonSend<WryWebViews>,
+) -> BrowsingDataResult {
+    let webview = web_views.get(&entity).ok_or(BrowsingDataError::WebviewNotFound(entity))?;
+    webview.clear_all_browsing_data()?;
+    Ok(())
+}
+
+/// Returns the cookies that would be sent to the current page, without the values of `HttpOnly` cookies,
+/// so that pages can't read the cookies of other origins or the values hidden from `document.cookie`.
+fn page_cookies_system(
+    In(entity): In<Entity>,
+    web_views: NonSend<WryWebViews>,
+) -> BrowsingDataResult<Vec<WebviewCookie>> {
+    let webview = web_views.get(&entity).ok_or(BrowsingDataError::WebviewNotFound(entity))?;
+    let url = webview.url()?;
+    if url.is_empty() {
+        return Err(BrowsingDataError::NoPage);
+    }
+    Ok(webview
+        .cookies_for_url(&url)?
+        .iter()
+        .map(WebviewCookie::from)
+        .map(hide_http_only_value)
+        .collect())
+}
+
+fn hide_http_only_value(mut cookie: WebviewCookie) -> WebviewCookie {
+    if cookie.http_only {
+        cookie.value.clear();
+    }
+    cookie
+}
+
+#[command(id = "FLURX|browsing_data::cookies")]
+fn cookies_command(WebviewEntity(entity): WebviewEntity) -> Action<Entity, BrowsingDataResult<Vec<WebviewCookie>>> {
+    once::run(page_cookies_system).with(entity)
+}
+
+#[command(id = "FLURX|browsing_data::set_cookie")]
+fn set_cookie_command(
+    In(cookie): In<WebviewCookie>,
+    WebviewEntity(entity): WebviewEntity,
+) -> Action<(Entity, WebviewCookie, bool), BrowsingDataResult> {
+    set_cookie(entity, cookie)
+}
+
+#[command(id = "FLURX|browsing_data::delete_cookie")]
+fn delete_cookie_command(
+    In(cookie): In<WebviewCookie>,
+    WebviewEntity(entity): WebviewEntity,
+) -> Action<(Entity, WebviewCookie, bool), BrowsingDataResult> {
+    delete_cookie(entity, cookie)
+}
+
+#[command(id = "FLURX|browsing_data::clear")]
+fn clear_command(WebviewEntity(entity): WebviewEntity) -> Action<Entity, BrowsingDataResult> {
+    clear_browsing_data(entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::browsing_data::{hide_http_only_value, CookieSameSite, WebviewCookie};
+
+    #[test]
+    fn session_cookie() {
+        assert_eq!(WebviewCookie::new("lang", "en").to_document_cookie(0), "lang=en");
+    }
+
+    #[test]
+    fn cookie_with_attributes() {
+        let cookie = WebviewCookie {
+            domain: Some("example.com".to_string()),
+            path: Some("/".to_string()),
+            secure: true,
+            same_site: Some(CookieSameSite::Lax),
+            expires: Some(160),
+            ..WebviewCookie::new("token", "abc")
+        };
+        assert_eq!(cookie.to_document_cookie(100), "token=abc; Domain=example.com; Path=/; Max-Age=60; Secure; SameSite=Lax");
+    }
+
+    #[test]
+    fn expired_cookie() {
+        let cookie = WebviewCookie {
+            expires: Some(10),
+            ..WebviewCookie::new("token", "")
+        };
+        assert_eq!(cookie.to_document_cookie(100), "token=; Max-Age=0");
+    }
+
+    #[test]
+    fn reject_attribute_injection() {
+        assert!(WebviewCookie::new("lang", "en").validate().is_ok());
+        assert!(WebviewCookie::new("lang", "\"en\"").validate().is_ok());
+        assert!(WebviewCookie::new("lang", "en; Domain=evil.com").validate().is_err());
+        assert!(WebviewCookie::new("lang; Path=/", "en").validate().is_err());
+        assert!(WebviewCookie::new("", "en").validate().is_err());
+        let cookie = WebviewCookie {
+            path: Some("/; Secure".to_string()),
+            ..WebviewCookie::new("lang", "en")
+        };
+        assert!(cookie.validate().is_err());
+    }
+
+    #[test]
+    fn hide_http_only() {
+        let cookie = WebviewCookie {
+            http_only: true,
+            ..WebviewCookie::new("session", "secret")
+        };
+        assert_eq!(hide_http_only_value(cookie).value, "");
+        assert_eq!(hide_http_only_value(WebviewCookie::new("lang", "en")).value, "en");
+    }
+}
@@ -0,0 +1,203 @@
+//! Forwards the console output and uncaught errors of webviews into `bevy::log`.
+
+use crate::webview::load_webview::WryInitializationScripts;
+use bevy::log;
+use bevy::prelude::{App, Entity, Event, EventReader, EventWriter, Name, Plugin, PreUpdate, Query, Reflect};
+use bevy_flurx_ipc::ipc_events::IpcEventExt;
+use bevy_flurx_ipc::prelude::IpcEvent;
+use serde::Deserialize;
+
+/// Forwards `console.*` output, uncaught exceptions and unhandled promise rejections
+/// of all webviews into `bevy::log`.
+///
+/// Each message is prefixed with the [`Name`] of the webview and the source location, for example,
+/// `[hud] flurx://localhost/main.js:10:5 message`.
+///
+/// Errors also fire [`WebviewScriptError`].
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin::default(),
+///         WebviewConsolePlugin,
+///     ));
+/// ```
+pub struct WebviewConsolePlugin;
+
+impl Plugin for WebviewConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<WebviewScriptError>()
+            .register_type::<ScriptErrorKind>()
+            .add_event::<WebviewScriptError>()
+            .add_ipc_event::<ConsoleMessage>("FLURX|console")
+            .add_systems(PreUpdate, forward_console_messages);
+        app
+            .world_mut()
+            .get_resource_or_insert_with(WryInitializationScripts::default)
+            .0
+            .push(include_str!("../../scripts/console.js").to_string());
+    }
+}
+
+/// Fired when an error occurred in the webview.
+///
+/// This requires [`WebviewConsolePlugin`].
+#[derive(Event, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct WebviewScriptError {
+    /// The entity associated with the webview in which the error occurred.
+    pub webview_entity: Entity,
+
+    /// How the error was reported.
+    pub kind: ScriptErrorKind,
+
+    /// The error message.
+    pub message: String,
+
+    /// The url of the script.
+    pub source: Option<String>,
+
+    /// The line number in the script.
+    pub line: Option<u32>,
+
+    /// The column number in the script.
+    pub column: Option<u32>,
+
+    /// The stack trace if the error is an `Error` object.
+    pub stack: Option<String>,
+}
+
+/// Represents how [`WebviewScriptError`] was reported.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScriptErrorKind {
+    /// `console.error` was called.
+    ConsoleError,
+
+    /// The exception was not caught.
+    Uncaught,
+
+    /// The promise was rejected without the handler.
+    UnhandledRejection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConsoleMessage {
+    level: ConsoleLevel,
+    kind: Option<ScriptErrorKind>,
+    message: String,
+    source: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    stack: Option<String>,
+    /// The stack captured in the console wrapper, from which the location of the caller is parsed.
+    caller_stack: Option<String>,
+}
+
+/// The name of the console wrapper function in `console.js`.
+const WRAPPER_NAME: &str = "__flurxConsole";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ConsoleLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+fn forward_console_messages(
+    mut er: EventReader<IpcEvent<ConsoleMessage>>,
+    mut ew: EventWriter<WebviewScriptError>,
+    names: Query<&Name>,
+) {
+    for IpcEvent { webview_entity, payload } in er.read() {
+        let name = names.get(*webview_entity).map_or("webview", |name| name.as_str());
+        let (source, line, column) = match payload.caller_stack.as_deref().and_then(caller_location) {
+            Some((source, line, column)) => (Some(source), Some(line), Some(column)),
+            None => (payload.source.clone(), payload.line, payload.column),
+        };
+        let message = match (source.as_ref(), line, column) {
+            (Some(source), Some(line), Some(column)) => format!("[{name}] {source}:{line}:{column} {}", payload.message),
+            (Some(source), Some(line), None) => format!("[{name}] {source}:{line} {}", payload.message),
+            _ => format!("[{name}] {}", payload.message),
+        };
+        match payload.level {
+            ConsoleLevel::Trace => log::trace!("{message}"),
+            ConsoleLevel::Debug => log::debug!("{message}"),
+            ConsoleLevel::Info => log::info!("{message}"),
+            ConsoleLevel::Warn => log::warn!("{message}"),
+            ConsoleLevel::Error => log::error!("{message}"),
+        }
+        if let Some(kind) = payload.kind {
+            ew.send(WebviewScriptError {
+                webview_entity: *webview_entity,
+                kind,
+                message: payload.message.clone(),
+                source,
+                line,
+                column,
+                stack: payload.stack.clone(),
+            });
+        }
+    }
+}
+
+/// Returns the location of the frame which called the console wrapper in `stack`.
+///
+/// The frames of the wrapper and the frames above it are skipped by their names,
+/// so this works regardless of how many frames the engine adds.
+fn caller_location(stack: &str) -> Option<(String, u32, u32)> {
+    let frames = stack.lines().collect::<Vec<_>>();
+    let wrapper = frames.iter().rposition(|frame| frame.contains(WRAPPER_NAME))?;
+    frames[wrapper + 1..].iter().find_map(|frame| parse_frame(frame))
+}
+
+/// Parses the frame of the stack trace such as `at main (https://example.com/main.js:10:5)` in Chromium
+/// or `main@https://example.com/main.js:10:5` in `WebKit`.
+fn parse_frame(frame: &str) -> Option<(String, u32, u32)> {
+    let frame = frame.trim().trim_end_matches(')');
+    let (rest, column) = frame.rsplit_once(':')?;
+    let (rest, line) = rest.rsplit_once(':')?;
+    let source = rest
+        .rsplit(['(', '@', ' '])
+        .next()
+        .filter(|source| !source.is_empty())?;
+    Some((source.to_string(), line.parse().ok()?, column.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::console::caller_location;
+
+    #[test]
+    fn caller_in_chromium() {
+        let stack = "Error\n    at console.__flurxConsole [as log] (<anonymous>:22:34)\n    at render (https://flurx.localhost/main.js:10:5)\n    at https://flurx.localhost/main.js:20:1";
+        assert_eq!(caller_location(stack), Some(("https://flurx.localhost/main.js".to_string(), 10, 5)));
+    }
+
+    #[test]
+    fn caller_in_webkit() {
+        let stack = "__flurxConsole@flurx://localhost/index.html:22:34\nrender@flurx://localhost/main.js:10:5\nglobal code@flurx://localhost/main.js:20:1";
+        assert_eq!(caller_location(stack), Some(("flurx://localhost/main.js".to_string(), 10, 5)));
+    }
+
+    #[test]
+    fn skip_frames_without_location() {
+        let stack = "__flurxConsole@\n[native code]\nmain@flurx://localhost/main.js:3:7";
+        assert_eq!(caller_location(stack), Some(("flurx://localhost/main.js".to_string(), 3, 7)));
+    }
+
+    #[test]
+    fn no_wrapper_frame() {
+        assert_eq!(caller_location("main@flurx://localhost/main.js:3:7"), None);
+    }
+}
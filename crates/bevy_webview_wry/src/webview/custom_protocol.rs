@@ -0,0 +1,244 @@
+//! Provides a mechanism to handle user-defined custom protocols in bevy.
+
+use crate::webview::handlers::WryEvents;
+use crate::webview::route::WebviewRoutes;
+use bevy::prelude::{App, Commands, Plugin, PreUpdate, Res, Resource, Update};
+use bevy::utils::HashMap;
+use bevy_flurx::prelude::{Action, ActionSeed, Reactor};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use wry::http::header::CONTENT_TYPE;
+use wry::RequestAsyncResponder;
+
+/// The request passed to the custom protocol handler.
+///
+/// The entity of the webview that sent the request can be obtained from
+/// the request extensions as [`WebviewEntity`](bevy_flurx_ipc::prelude::WebviewEntity).
+pub type ProtocolRequest = wry::http::Request<Vec<u8>>;
+
+/// The response returned from the custom protocol handler.
+pub type ProtocolResponse = wry::http::Response<Vec<u8>>;
+
+/// Converts the value returned from handlers into [`ProtocolResponse`].
+pub trait IntoProtocolResponse {
+    /// Converts itself into [`ProtocolResponse`].
+    fn into_response(self) -> ProtocolResponse;
+}
+
+impl IntoProtocolResponse for ProtocolResponse {
+    #[inline]
+    fn into_response(self) -> ProtocolResponse {
+        self
+    }
+}
+
+impl IntoProtocolResponse for Vec<u8> {
+    fn into_response(self) -> ProtocolResponse {
+        with_content_type("application/octet-stream", self)
+    }
+}
+
+impl IntoProtocolResponse for String {
+    fn into_response(self) -> ProtocolResponse {
+        with_content_type("text/plain", self.into_bytes())
+    }
+}
+
+/// Serializes the value as the json response.
+#[repr(transparent)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> IntoProtocolResponse for Json<T> {
+    fn into_response(self) -> ProtocolResponse {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => with_content_type("application/json", body),
+            Err(e) => {
+                let mut response = with_content_type("text/plain", e.to_string().into_bytes());
+                *response.status_mut() = wry::http::StatusCode::INTERNAL_SERVER_ERROR;
+                response
+            }
+        }
+    }
+}
+
+impl<T, E> IntoProtocolResponse for Result<T, E>
+where
+    T: IntoProtocolResponse,
+    E: IntoProtocolResponse,
+{
+    fn into_response(self) -> ProtocolResponse {
+        match self {
+            Ok(ok) => ok.into_response(),
+            Err(e) => {
+                let mut response = e.into_response();
+                if response.status().is_success() {
+                    *response.status_mut() = wry::http::StatusCode::INTERNAL_SERVER_ERROR;
+                }
+                response
+            }
+        }
+    }
+}
+
+fn with_content_type(content_type: &str, body: Vec<u8>) -> ProtocolResponse {
+    let mut response = ProtocolResponse::new(body);
+    if let Ok(value) = content_type.parse() {
+        response.headers_mut().insert(CONTENT_TYPE, value);
+    }
+    response
+}
+
+type BoxedSyncHandler = Box<dyn Fn(ProtocolRequest) -> ProtocolResponse + Send + Sync>;
+
+type BoxedActionHandler = Box<dyn Fn() -> ActionSeed<ProtocolRequest, ProtocolResponse> + Send + Sync>;
+
+/// The handler of the custom protocol registered by [`WebviewProtocolExt::add_webview_protocol`].
+pub struct ProtocolHandler(HandlerKind);
+
+enum HandlerKind {
+    Sync(BoxedSyncHandler),
+    Action(BoxedActionHandler),
+}
+
+impl ProtocolHandler {
+    /// Creates the handler that responds immediately on the thread which received the request.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// ProtocolHandler::sync(|_request| {
+    ///     ProtocolResponse::new(b"hello".to_vec())
+    /// });
+    /// ```
+    pub fn sync(f: impl Fn(ProtocolRequest) -> ProtocolResponse + Send + Sync + 'static) -> Self {
+        Self(HandlerKind::Sync(Box::new(f)))
+    }
+
+    /// Creates the handler that runs the action in the [`World`](bevy::prelude::World) and responds with its output.
+    ///
+    /// `f` is called every time a request is received.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct Score(usize);
+    ///
+    /// ProtocolHandler::action(|| {
+    ///     once::run(|In(_request): In<ProtocolRequest>, score: Res<Score>| {
+    ///         ProtocolResponse::new(score.0.to_string().into_bytes())
+    ///     })
+    /// });
+    /// ```
+    pub fn action<A>(f: impl Fn() -> A + Send + Sync + 'static) -> Self
+    where
+        A: Into<ActionSeed<ProtocolRequest, ProtocolResponse>>,
+    {
+        Self(HandlerKind::Action(Box::new(move || f().into())))
+    }
+}
+
+/// Registers the custom protocols handled by bevy.
+pub trait WebviewProtocolExt {
+    /// Registers the custom protocol which is available in all webviews.
+    ///
+    /// After registering, the webview can fetch resources such as `<scheme>://localhost/<path>`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `scheme` is `flurx` since it is reserved for local resources.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// App::new()
+    ///     .add_plugins((DefaultPlugins, WebviewWryPlugin::default()))
+    ///     .add_webview_protocol("game", ProtocolHandler::sync(|request| {
+    ///         ProtocolResponse::new(request.uri().path().as_bytes().to_vec())
+    ///     }));
+    /// ```
+    fn add_webview_protocol(&mut self, scheme: impl Into<String>, handler: ProtocolHandler) -> &mut Self;
+}
+
+impl WebviewProtocolExt for App {
+    fn add_webview_protocol(&mut self, scheme: impl Into<String>, handler: ProtocolHandler) -> &mut Self {
+        let scheme = scheme.into();
+        assert_ne!(scheme, "flurx", "`flurx` is reserved for local resources.");
+        self
+            .world_mut()
+            .get_resource_or_insert_with::<WebviewProtocols>(WebviewProtocols::default)
+            .0
+            .insert(scheme, Arc::new(handler));
+        self
+    }
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct WebviewProtocols(pub HashMap<String, Arc<ProtocolHandler>>);
+
+pub(crate) struct PendingProtocolRequest {
+    seed: ActionSeed<(), ProtocolResponse>,
+    responder: Mutex<RequestAsyncResponder>,
+}
+
+impl PendingProtocolRequest {
+    pub(crate) fn new<I>(action: Action<I, ProtocolResponse>, responder: RequestAsyncResponder) -> Self
+    where
+        I: Send + Sync + 'static,
+    {
+        Self {
+            seed: ActionSeed::from(move |_, output| action.create_runner(output)),
+            responder: Mutex::new(responder),
+        }
+    }
+}
+
+impl WryEvents<PendingProtocolRequest> {
+    pub(crate) fn handle(
+        &self,
+        handler: &ProtocolHandler,
+        request: ProtocolRequest,
+        responder: RequestAsyncResponder,
+    ) {
+        match &handler.0 {
+            HandlerKind::Sync(f) => responder.respond(f(request)),
+            HandlerKind::Action(f) => self.push(PendingProtocolRequest::new(f().with(request), responder)),
+        }
+    }
+}
+
+pub(crate) struct CustomProtocolPlugin;
+
+impl Plugin for CustomProtocolPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WebviewProtocols>()
+            .init_resource::<WebviewRoutes>()
+            .init_resource::<WryEvents<PendingProtocolRequest>>()
+            .add_systems(PreUpdate, run_protocol_actions);
+    }
+}
+
+fn run_protocol_actions(
+    mut commands: Commands,
+    requests: Res<WryEvents<PendingProtocolRequest>>,
+) {
+    for PendingProtocolRequest { seed, responder } in requests.take_events() {
+        commands.spawn(Reactor::schedule(move |task| async move {
+            let response = task.will(Update, seed).await;
+            if let Ok(responder) = responder.into_inner() {
+                responder.respond(response);
+            }
+        }));
+    }
+}
@@ -1,51 +1,238 @@
-use crate::webview::WryWebViews;
-use bevy::prelude::{
-    App, Commands, Component, DetectChanges, Entity, NonSend, Plugin,
-    PostUpdate, Query, Reflect,
-    ReflectComponent,
-};
-use bevy_webview_core::prelude::{IsOpenDevtools, UseDevtools};
+//! Opens and closes the devtools of webviews.
+//!
+//! Devtools are available in debug builds, or in release builds with the `devtools` feature.
+//! On macOS, the feature uses private APIs, so don't enable it in builds submitted to the App Store.
 
-pub struct DevtoolsPlugin;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::{App, Entity, Event, Plugin, Reflect};
+
+/// The key combination that toggles the devtools of the focused webview.
+///
+/// The shortcut is handled in the page, so it works while the webview has keyboard focus,
+/// and the key event is not passed to the page.
+/// Webviews whose [`UseDevtools`](bevy_webview_core::prelude::UseDevtools) is `false` ignore it.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// WebviewWryPlugin {
+///     devtools_shortcut: Some(DevtoolsShortcut::new(KeyCode::KeyI).with_ctrl().with_shift()),
+///     ..default()
+/// };
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Reflect)]
+pub struct DevtoolsShortcut {
+    /// The physical key, which corresponds to `KeyboardEvent.code`.
+    pub key: KeyCode,
+
+    /// Whether `Ctrl` must be pressed.
+    pub ctrl: bool,
+
+    /// Whether `Shift` must be pressed.
+    pub shift: bool,
+
+    /// Whether `Alt` must be pressed.
+    pub alt: bool,
+
+    /// Whether `Meta`(`Command` on macOS) must be pressed.
+    pub meta: bool,
+}
+
+impl DevtoolsShortcut {
+    /// Creates the shortcut of `key` without modifiers.
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+        }
+    }
+
+    /// Requires `Ctrl` to be pressed.
+    pub const fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Requires `Shift` to be pressed.
+    pub const fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Requires `Alt` to be pressed.
+    pub const fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Requires `Meta` to be pressed.
+    pub const fn with_meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    #[cfg(any(debug_assertions, feature = "devtools"))]
+    fn to_script(&self) -> String {
+        let shortcut = serde_json::json!({
+            "code": format!("{:?}", self.key),
+            "ctrl": self.ctrl,
+            "shift": self.shift,
+            "alt": self.alt,
+            "meta": self.meta,
+        });
+        include_str!("../../scripts/devtools.js").replace("__SHORTCUT__", &shortcut.to_string())
+    }
+}
+
+/// Fired when the devtools of the webview are opened.
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect)]
+pub struct DevtoolsOpened {
+    /// The entity associated with the webview whose devtools were opened.
+    pub webview_entity: Entity,
+}
+
+/// Fired when the devtools of the webview are closed.
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect)]
+pub struct DevtoolsClosed {
+    /// The entity associated with the webview whose devtools were closed.
+    pub webview_entity: Entity,
+}
+
+pub(crate) struct DevtoolsPlugin {
+    #[cfg_attr(not(any(debug_assertions, feature = "devtools")), allow(dead_code))]
+    pub shortcut: Option<DevtoolsShortcut>,
+}
 
 impl Plugin for DevtoolsPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<DevtoolsReady>()
-            .add_systems(PostUpdate, change_open_devtools);
-    }
-}
-
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct DevtoolsReady;
-
-fn change_open_devtools(
-    mut commands: Commands,
-    mut views: Query<(
-        Entity,
-        &mut IsOpenDevtools,
-        &UseDevtools,
-        Option<&DevtoolsReady>,
-    )>,
-    web_views: NonSend<WryWebViews>,
-) {
-    for (entity, mut is_open, use_devtools, ready) in views.iter_mut() {
-        if !use_devtools.0 {
-            is_open.0 = false;
-            continue;
+        app
+            .register_type::<DevtoolsOpened>()
+            .register_type::<DevtoolsClosed>()
+            .add_event::<DevtoolsOpened>()
+            .add_event::<DevtoolsClosed>();
+
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        {
+            use bevy::prelude::{PostUpdate, PreUpdate};
+            use bevy_flurx_ipc::ipc_events::IpcEventExt;
+
+            app
+                .register_type::<enabled::DevtoolsReady>()
+                .add_ipc_event::<enabled::ToggleDevtools>("FLURX|devtools::toggle")
+                .add_systems(PreUpdate, enabled::toggle_devtools)
+                .add_systems(PostUpdate, enabled::change_open_devtools);
+            if let Some(shortcut) = self.shortcut.as_ref() {
+                app
+                    .world_mut()
+                    .get_resource_or_insert_with(crate::webview::load_webview::WryInitializationScripts::default)
+                    .0
+                    .push(shortcut.to_script());
+            }
+        }
+    }
+}
+
+#[cfg(any(debug_assertions, feature = "devtools"))]
+mod enabled {
+    use crate::prelude::{DevtoolsClosed, DevtoolsOpened};
+    use crate::webview::WryWebViews;
+    use bevy::prelude::{
+        Commands, Component, DetectChanges, DetectChangesMut, Entity, EventReader, EventWriter, NonSend,
+        Query, Reflect, ReflectComponent,
+    };
+    use bevy_flurx_ipc::prelude::IpcEvent;
+    use bevy_webview_core::prelude::{IsOpenDevtools, UseDevtools};
+    use serde::Deserialize;
+
+    /// Holds whether the devtools were open in the last frame.
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    pub(super) struct DevtoolsReady(bool);
+
+    #[derive(Deserialize)]
+    pub(super) struct ToggleDevtools {}
+
+    pub(super) fn toggle_devtools(
+        mut er: EventReader<IpcEvent<ToggleDevtools>>,
+        mut views: Query<(&mut IsOpenDevtools, &UseDevtools)>,
+        web_views: NonSend<WryWebViews>,
+    ) {
+        for IpcEvent { webview_entity, .. } in er.read() {
+            let Ok((mut is_open, use_devtools)) = views.get_mut(*webview_entity) else {
+                continue;
+            };
+            let Some(webview) = web_views.0.get(webview_entity) else {
+                continue;
+            };
+            if use_devtools.0 {
+                is_open.0 = !webview.is_devtools_open();
+            }
         }
-        let Some(webview) = web_views.0.get(&entity) else {
-            continue;
-        };
-        if ready.is_none() || is_open.is_changed() {
-            if is_open.0 {
-                webview.open_devtools();
-            } else {
-                webview.close_devtools();
+    }
+
+    pub(super) fn change_open_devtools(
+        mut commands: Commands,
+        mut views: Query<(
+            Entity,
+            &mut IsOpenDevtools,
+            &UseDevtools,
+            Option<&mut DevtoolsReady>,
+        )>,
+        mut opened: EventWriter<DevtoolsOpened>,
+        mut closed: EventWriter<DevtoolsClosed>,
+        web_views: NonSend<WryWebViews>,
+    ) {
+        for (webview_entity, mut is_open, use_devtools, ready) in views.iter_mut() {
+            if !use_devtools.0 {
+                is_open.set_if_neq(IsOpenDevtools(false));
+                continue;
+            }
+            let Some(webview) = web_views.0.get(&webview_entity) else {
+                continue;
+            };
+            match ready {
+                Some(mut ready) if !is_open.is_changed() => {
+                    let open = webview.is_devtools_open();
+                    is_open.set_if_neq(IsOpenDevtools(open));
+                    if ready.0 == open {
+                        continue;
+                    }
+                    ready.0 = open;
+                    if open {
+                        opened.send(DevtoolsOpened { webview_entity });
+                    } else {
+                        closed.send(DevtoolsClosed { webview_entity });
+                    }
+                }
+                ready => {
+                    if is_open.0 {
+                        webview.open_devtools();
+                    } else {
+                        webview.close_devtools();
+                    }
+                    if ready.is_none() {
+                        commands.entity(webview_entity).insert(DevtoolsReady(false));
+                    }
+                }
             }
-            commands.entity(entity).insert(DevtoolsReady);
-        } else {
-            is_open.0 = webview.is_devtools_open();
         }
     }
 }
+
+#[cfg(all(test, any(debug_assertions, feature = "devtools")))]
+mod tests {
+    use crate::webview::devtools::DevtoolsShortcut;
+    use bevy::input::keyboard::KeyCode;
+
+    #[test]
+    fn embed_shortcut_into_script() {
+        let script = DevtoolsShortcut::new(KeyCode::KeyI).with_ctrl().with_shift().to_script();
+        assert!(script.contains(r#"const shortcut = {"alt":false,"code":"KeyI","ctrl":true,"meta":false,"shift":true};"#));
+    }
+}
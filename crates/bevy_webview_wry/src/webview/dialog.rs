@@ -0,0 +1,356 @@
+//! Routes `alert`, `confirm` and `prompt` of webviews to bevy.
+
+use crate::webview::handlers::WryEvents;
+use bevy::prelude::{App, Component, Entity, Event, EventReader, EventWriter, Plugin, PreUpdate, Reflect, ReflectComponent, ReflectDefault, Res};
+use bevy_flurx_ipc::ipc_events::IpcEventExt;
+use bevy_flurx_ipc::prelude::{IpcEvent, IpcResolveEvent};
+use serde::Deserialize;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Overrides `alert`, `confirm` and `prompt` of webviews with [`WebviewDialogs`] so that they fire [`WebviewDialogRequested`]
+/// instead of showing the native dialogs.
+///
+/// Since the answer is decided in systems, the overridden functions return a `Promise`,
+/// and **callers must `await` them**.
+/// A `Promise` is always truthy, so synchronous code such as `if (confirm("..."))` proceeds without waiting
+/// for the answer; don't add [`WebviewDialogs`] to webviews whose pages call them synchronously.
+///
+/// ```js
+/// if (await confirm("Quit the match?")) {
+///     const name = await prompt("Your name", "player");
+/// }
+/// ```
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin::default(),
+///         WebviewDialogPlugin::default(),
+///     ))
+///     .add_systems(Startup, spawn_webview)
+///     .add_systems(Update, answer_dialogs);
+///
+/// fn spawn_webview(mut commands: Commands) {
+///     commands.spawn((
+///         Webview::default(),
+///         WebviewDialogs,
+///     ));
+/// }
+///
+/// fn answer_dialogs(mut er: EventReader<WebviewDialogRequested>) {
+///     for request in er.read() {
+///         if let WebviewDialogKind::Prompt { .. } = request.kind {
+///             request.respond_with_text("player");
+///         } else {
+///             request.respond(true);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct WebviewDialogPlugin {
+    /// If `true`, all requests are answered by the `rfd` dialogs of [`bevy_flurx_api::dialog`].
+    ///
+    /// See [`WebviewDialogRequested::respond_with_rfd`].
+    #[cfg(feature = "api")]
+    pub rfd_fallback: bool,
+}
+
+impl Plugin for WebviewDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<WebviewDialogs>()
+            .register_type::<WebviewDialogRequested>()
+            .register_type::<WebviewDialogKind>()
+            .add_event::<WebviewDialogRequested>()
+            .init_resource::<WryEvents<DialogAnswer>>()
+            .add_ipc_event::<DialogPayload>("FLURX|dialog")
+            .add_systems(PreUpdate, (
+                request_dialogs,
+                resolve_dialogs,
+            ));
+        #[cfg(feature = "api")]
+        if self.rfd_fallback {
+            app.add_systems(bevy::prelude::Update, respond_with_rfd);
+        }
+    }
+}
+
+/// Marks the webview whose `alert`, `confirm` and `prompt` are overridden by [`WebviewDialogPlugin`].
+///
+/// This must be inserted before the webview is created, and requires [`WebviewDialogPlugin`].
+#[derive(Component, Default, Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct WebviewDialogs;
+
+/// Fired when `alert`, `confirm` or `prompt` is called in the webview.
+///
+/// This requires [`WebviewDialogPlugin`].
+/// The `Promise` returned in the webview stays pending until [`WebviewDialogRequested::respond`]
+/// or [`WebviewDialogRequested::respond_with_text`] is called.
+/// To decide the answer over multiple frames, for example, with an in-game UI, keep a clone of this event.
+///
+/// If all clones are dropped without the answer, the dialog is closed with the default answer:
+/// `confirm` returns `false` and `prompt` returns `null`.
+#[derive(Event, Clone, Debug, Reflect)]
+pub struct WebviewDialogRequested {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// The kind of the dialog.
+    pub kind: WebviewDialogKind,
+
+    /// The message passed to the dialog.
+    pub message: String,
+
+    #[reflect(ignore)]
+    responder: DialogResponder,
+}
+
+impl WebviewDialogRequested {
+    /// Closes the dialog.
+    ///
+    /// Only the first call of the respond methods takes effect.
+    ///
+    /// - [`WebviewDialogKind::Alert`]: `accepted` is ignored.
+    /// - [`WebviewDialogKind::Confirm`]: `confirm` returns `accepted`.
+    /// - [`WebviewDialogKind::Prompt`]: `prompt` returns the default value, or an empty string if it is not specified,
+    ///   when `accepted` is `true`, otherwise `null`.
+    pub fn respond(&self, accepted: bool) {
+        let output = match (&self.kind, accepted) {
+            (WebviewDialogKind::Confirm, accepted) => serde_json::Value::Bool(accepted),
+            (WebviewDialogKind::Prompt { default_value }, true) => default_value.clone().unwrap_or_default().into(),
+            (WebviewDialogKind::Alert, _) | (WebviewDialogKind::Prompt { .. }, false) => serde_json::Value::Null,
+        };
+        self.answer(output);
+    }
+
+    /// Closes the dialog with `text` as the input of `prompt`.
+    ///
+    /// For [`WebviewDialogKind::Alert`] and [`WebviewDialogKind::Confirm`], this is the same as `respond(true)`.
+    pub fn respond_with_text(&self, text: impl Into<String>) {
+        match self.kind {
+            WebviewDialogKind::Prompt { .. } => self.answer(serde_json::Value::String(text.into())),
+            _ => self.respond(true),
+        }
+    }
+
+    /// Shows the `rfd` dialog of [`bevy_flurx_api::dialog`] and responds with its result.
+    ///
+    /// This blocks until the dialog is closed.
+    ///
+    /// Since `rfd` has no text input, `prompt` returns `null`.
+    #[cfg(feature = "api")]
+    pub fn respond_with_rfd(&self) {
+        use bevy_flurx_api::dialog::{show_confirm_dialog, show_message_dialog};
+        match self.kind {
+            WebviewDialogKind::Alert => {
+                show_message_dialog(&self.message);
+                self.respond(true);
+            }
+            WebviewDialogKind::Confirm => self.respond(show_confirm_dialog(&self.message)),
+            WebviewDialogKind::Prompt { .. } => {
+                bevy::log::warn!("`prompt` can't be shown with rfd dialogs; it returns `null`.");
+                self.respond(false);
+            }
+        }
+    }
+
+    fn answer(&self, output: serde_json::Value) {
+        self.responder.0.answer(output);
+    }
+}
+
+/// The kind of [`WebviewDialogRequested`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Reflect)]
+pub enum WebviewDialogKind {
+    /// `alert` was called.
+    Alert,
+
+    /// `confirm` was called.
+    Confirm,
+
+    /// `prompt` was called.
+    Prompt {
+        /// The default value of the input.
+        default_value: Option<String>,
+    },
+}
+
+/// Shared by the clones of [`WebviewDialogRequested`].
+#[derive(Clone)]
+struct DialogResponder(Arc<DialogResponderInner>);
+
+impl Default for DialogResponder {
+    fn default() -> Self {
+        Self(Arc::new(DialogResponderInner {
+            answers: WryEvents::default(),
+            webview_entity: Entity::PLACEHOLDER,
+            resolve_id: 0,
+            default_output: serde_json::Value::Null,
+            responded: AtomicBool::new(true),
+        }))
+    }
+}
+
+impl Debug for DialogResponder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DialogResponder")
+            .field("resolve_id", &self.0.resolve_id)
+            .field("responded", &self.0.responded.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+struct DialogResponderInner {
+    answers: WryEvents<DialogAnswer>,
+    webview_entity: Entity,
+    resolve_id: usize,
+    /// The answer sent if the request is dropped without the answer.
+    default_output: serde_json::Value,
+    responded: AtomicBool,
+}
+
+impl DialogResponderInner {
+    fn answer(&self, output: serde_json::Value) {
+        if self.responded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.answers.push(DialogAnswer {
+            webview_entity: self.webview_entity,
+            resolve_id: self.resolve_id,
+            output: output.to_string(),
+        });
+    }
+}
+
+impl Drop for DialogResponderInner {
+    fn drop(&mut self) {
+        // Otherwise, the `Promise` in the webview never settles.
+        let output = std::mem::take(&mut self.default_output);
+        self.answer(output);
+    }
+}
+
+struct DialogAnswer {
+    webview_entity: Entity,
+    resolve_id: usize,
+    output: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DialogPayload {
+    resolve_id: usize,
+    kind: DialogPayloadKind,
+    message: String,
+    default_value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum DialogPayloadKind {
+    Alert,
+    Confirm,
+    Prompt,
+}
+
+fn request_dialogs(
+    mut er: EventReader<IpcEvent<DialogPayload>>,
+    mut ew: EventWriter<WebviewDialogRequested>,
+    answers: Res<WryEvents<DialogAnswer>>,
+) {
+    for IpcEvent { webview_entity, payload } in er.read() {
+        let (kind, default_output) = match payload.kind {
+            DialogPayloadKind::Alert => (WebviewDialogKind::Alert, serde_json::Value::Null),
+            DialogPayloadKind::Confirm => (WebviewDialogKind::Confirm, serde_json::Value::Bool(false)),
+            DialogPayloadKind::Prompt => (WebviewDialogKind::Prompt {
+                default_value: payload.default_value.clone(),
+            }, serde_json::Value::Null),
+        };
+        ew.send(WebviewDialogRequested {
+            webview_entity: *webview_entity,
+            kind,
+            message: payload.message.clone(),
+            responder: DialogResponder(Arc::new(DialogResponderInner {
+                answers: answers.clone(),
+                webview_entity: *webview_entity,
+                resolve_id: payload.resolve_id,
+                default_output,
+                responded: AtomicBool::new(false),
+            })),
+        });
+    }
+}
+
+fn resolve_dialogs(
+    mut ew: EventWriter<IpcResolveEvent>,
+    answers: Res<WryEvents<DialogAnswer>>,
+) {
+    for DialogAnswer { webview_entity, resolve_id, output } in answers.take_events() {
+        ew.send(IpcResolveEvent {
+            entity: webview_entity,
+            resolve_id,
+            output,
+        });
+    }
+}
+
+#[cfg(feature = "api")]
+fn respond_with_rfd(mut er: EventReader<WebviewDialogRequested>) {
+    for request in er.read() {
+        request.respond_with_rfd();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::dialog::{DialogAnswer, DialogResponder, DialogResponderInner, WebviewDialogKind, WebviewDialogRequested};
+    use crate::webview::handlers::WryEvents;
+    use bevy::prelude::Entity;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    fn confirm_request(answers: &WryEvents<DialogAnswer>) -> WebviewDialogRequested {
+        WebviewDialogRequested {
+            webview_entity: Entity::PLACEHOLDER,
+            kind: WebviewDialogKind::Confirm,
+            message: "Quit?".to_string(),
+            responder: DialogResponder(Arc::new(DialogResponderInner {
+                answers: answers.clone(),
+                webview_entity: Entity::PLACEHOLDER,
+                resolve_id: 1,
+                default_output: serde_json::Value::Bool(false),
+                responded: AtomicBool::new(false),
+            })),
+        }
+    }
+
+    #[test]
+    fn answer_only_once() {
+        let answers = WryEvents::default();
+        let request = confirm_request(&answers);
+        request.clone().respond(true);
+        drop(request);
+        let answers = answers.take_events();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].output, "true");
+    }
+
+    #[test]
+    fn answer_default_when_dropped() {
+        let answers = WryEvents::default();
+        drop(confirm_request(&answers));
+        let answers = answers.take_events();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].output, "false");
+    }
+}
@@ -8,7 +8,8 @@ use crate::webview::handlers::download::{
     DownloadCompleted, DownloadPlugin, DownloadStarted,
 };
 use crate::webview::handlers::dragdrop::{DragDropPlugin, WryDragDrop};
-use crate::webview::handlers::navigation::{Navigated, NavigationPlugin};
+use crate::webview::handlers::navigation::{open_externally, Navigated, NavigationBlocked, NavigationPlugin, NavigationPolicies};
+use crate::webview::handlers::navigation_request::{DeferredNavigations, NavigationRequestKind, NavigationRequestPlugin};
 use crate::webview::handlers::new_window_request::{
     NewWindowRequested, NewWindowRequestedPlugin,
 };
@@ -19,7 +20,7 @@ use bevy::ecs::system::SystemParam;
 use bevy::math::IVec2;
 use bevy::prelude::{App, Entity, Event, EventWriter, Mut, Plugin, PreUpdate, Res, Resource};
 use bevy::reflect::GetTypeRegistration;
-use bevy_webview_core::prelude::{DragDropEvent, DragEntered, DragLeave, DragOver, Dropped};
+use bevy_webview_core::prelude::{DragDropEvent, DragEntered, DragLeave, DragOver, Dropped, NavigationAction};
 use std::sync::{Arc, Mutex};
 use wry::{PageLoadEvent, WebViewBuilder};
 
@@ -27,6 +28,7 @@ pub mod document_title_changed;
 pub mod download;
 pub mod dragdrop;
 pub mod navigation;
+pub mod navigation_request;
 pub mod new_window_request;
 pub mod page_load;
 
@@ -35,9 +37,10 @@ pub mod prelude {
     pub use crate::webview::handlers::{
         document_title_changed::DocumentTitleChanged,
         download::{DownloadCompleted, DownloadStarted},
-        navigation::Navigated,
+        navigation::{Navigated, NavigationBlocked},
+        navigation_request::{NavigationRequestKind, NavigationRequested},
         new_window_request::*,
-        page_load::{PageLoadFinished, PageLoadStarted},
+        page_load::{PageLoadFailed, PageLoadFinished, PageLoadStarted},
     };
 }
 
@@ -50,6 +53,7 @@ impl Plugin for WryHandlersPlugin {
             DragDropPlugin,
             PageLoadPlugin,
             NavigationPlugin,
+            NavigationRequestPlugin,
             DownloadPlugin,
             NewWindowRequestedPlugin,
         ));
@@ -87,7 +91,7 @@ impl<T> Clone for WryEvents<T> {
     }
 }
 
-trait RegisterWryEvent {
+pub(crate) trait RegisterWryEvent {
     fn register_wry_event<E: Event + GetTypeRegistration>(&mut self) -> &mut Self;
 }
 
@@ -121,6 +125,9 @@ pub(crate) struct WryEventParams<'w> {
     document_title_events: Res<'w, WryEvents<DocumentTitleChanged>>,
     drag_drop_events: Res<'w, WryEvents<WryDragDrop>>,
     navigation_events: Res<'w, WryEvents<Navigated>>,
+    navigation_blocked_events: Res<'w, WryEvents<NavigationBlocked>>,
+    navigation_policies: Res<'w, NavigationPolicies>,
+    deferred_navigations: Res<'w, DeferredNavigations>,
     download_started_events: Res<'w, WryEvents<DownloadStarted>>,
     download_completed_events: Res<'w, WryEvents<DownloadCompleted>>,
     new_win_req_events: Res<'w, WryEvents<NewWindowRequested>>,
@@ -137,11 +144,12 @@ impl WryEventParams<'_> {
             mut on_new_window_request
         ): HandlerQueryArgs,
         builder: WebViewBuilder<'a>,
+        initial_url: Option<&str>,
     ) -> WebViewBuilder<'a> {
         let builder = self.feed_page_load(webview_entity, builder);
         let builder = self.feed_document_title_changed(webview_entity, builder);
         let builder = self.feed_dragdrop(webview_entity, builder, &mut on_dragdrop);
-        let builder = self.feed_navigation(webview_entity, builder, &mut on_navigation);
+        let builder = self.feed_navigation(webview_entity, builder, &mut on_navigation, initial_url);
         let builder = self.feed_download(webview_entity, builder, &mut on_download);
         self.feed_new_window_request(webview_entity, builder, &mut on_new_window_request)
     }
@@ -153,10 +161,12 @@ impl WryEventParams<'_> {
     ) -> WebViewBuilder<'a> {
         let started_events = self.page_load_started_events.clone();
         let finished_events = self.page_load_finished_events.clone();
+        let deferred = self.deferred_navigations.clone();
         builder.with_on_page_load_handler(move |event, url| {
             let url = PassedUrl(url);
             match event {
                 PageLoadEvent::Started => {
+                    deferred.expire(webview_entity, &url.0);
                     started_events.push(PageLoadStarted {
                         webview_entity,
                         url,
@@ -166,6 +176,7 @@ impl WryEventParams<'_> {
                     finished_events.push(PageLoadFinished {
                         webview_entity,
                         url,
+                        status: None,
                     });
                 }
             }
@@ -211,17 +222,48 @@ impl WryEventParams<'_> {
         webview_entity: Entity,
         builder: WebViewBuilder<'a>,
         on_navigation: &mut OnNavigation,
+        initial_url: Option<&str>,
     ) -> WebViewBuilder<'a> {
+        let deferred = on_navigation.is_deferred().then(|| self.deferred_navigations.clone());
+        if let (Some(deferred), Some(url)) = (deferred.as_ref(), initial_url) {
+            deferred.approve(webview_entity, url.to_string(), NavigationRequestKind::Navigation);
+        }
         let on_navigation = on_navigation.take().unwrap_or(Box::new(|_| true));
 
         let events = self.navigation_events.clone();
+        let blocked_events = self.navigation_blocked_events.clone();
+        let policies = self.navigation_policies.clone();
         builder.with_navigation_handler(move |uri| {
             // FIXME: Not sure why, but sending ipc-command sometimes sends this uri.
             if uri == "flurx://localhost/?" {
                 return false;
             }
 
+            let opened_externally = match policies.evaluate(webview_entity, &uri) {
+                NavigationAction::Allow => None,
+                NavigationAction::Deny => Some(false),
+                NavigationAction::OpenExternally => Some(open_externally(&uri)),
+            };
             let uri = PassedUrl(uri);
+            if let Some(opened_externally) = opened_externally {
+                blocked_events.push(NavigationBlocked {
+                    webview_entity,
+                    uri,
+                    opened_externally,
+                });
+                return false;
+            }
+            if let Some(deferred) = deferred.as_ref() {
+                if !deferred.take_approval(webview_entity, &uri.0, NavigationRequestKind::Navigation) {
+                    deferred.request(webview_entity, uri.0, NavigationRequestKind::Navigation);
+                    return false;
+                }
+                events.push(Navigated {
+                    webview_entity,
+                    uri,
+                });
+                return true;
+            }
             let allow_navigation = on_navigation(uri.clone());
             if allow_navigation {
                 events.push(Navigated {
@@ -239,14 +281,23 @@ impl WryEventParams<'_> {
         builder: WebViewBuilder<'a>,
         on_download: &mut OnDownload,
     ) -> WebViewBuilder<'a> {
+        let deferred = on_download.is_deferred().then(|| self.deferred_navigations.clone());
         let mut on_download = on_download.take().unwrap_or(Box::new(|_, _| true));
 
         let started = self.download_started_events.clone();
         let finished = self.download_completed_events.clone();
         builder
             .with_download_started_handler(move |source_url, dest| {
+                let allow_download = match deferred.as_ref() {
+                    Some(deferred) if deferred.take_approval(webview_entity, &source_url, NavigationRequestKind::Download) => true,
+                    Some(deferred) => {
+                        deferred.request(webview_entity, source_url.clone(), NavigationRequestKind::Download);
+                        false
+                    }
+                    None => on_download(PassedUrl(source_url.clone()), dest),
+                };
                 let source_url = PassedUrl(source_url);
-                if on_download(source_url.clone(), dest) {
+                if allow_download {
                     started.push(DownloadStarted {
                         webview_entity,
                         source_url,
@@ -274,23 +325,28 @@ impl WryEventParams<'_> {
         on_new_window_request: &mut OnNewWindowRequest,
     ) -> WebViewBuilder<'a> {
         let events = self.new_win_req_events.clone();
+        let deferred = on_new_window_request.is_deferred().then(|| self.deferred_navigations.clone());
         let on_new_window_request = on_new_window_request
             .take()
             .unwrap_or(Box::new(|_| NewWindowResponse::Allow));
 
         builder.with_new_window_req_handler(move |url| {
+            if let Some(deferred) = deferred.as_ref() {
+                deferred.request(webview_entity, url, NavigationRequestKind::NewWindow);
+                return false;
+            }
             let url = PassedUrl(url);
             match on_new_window_request(url.clone()) {
-                NewWindowResponse::CreateWindow(window) => {
+                NewWindowResponse::Allow => true,
+                NewWindowResponse::Deny => false,
+                response => {
                     events.push(NewWindowRequested {
                         webview_entity,
                         url,
-                        window,
+                        response,
                     });
                     false
                 }
-                NewWindowResponse::Allow => true,
-                NewWindowResponse::Deny => false,
             }
         })
     }
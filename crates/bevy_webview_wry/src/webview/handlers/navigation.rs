@@ -1,8 +1,14 @@
 //! Controls navigation events.
 
 use crate::prelude::PassedUrl;
+use crate::util::glob_match;
 use crate::webview::handlers::RegisterWryEvent;
-use bevy::prelude::{App, Entity, Event, Plugin, Reflect};
+use bevy::log::{error, warn};
+use bevy::prelude::{App, Changed, Entity, Event, First, Plugin, Query, Reflect, RemovedComponents, Res, Resource};
+use bevy::utils::HashMap;
+use bevy_webview_core::prelude::{NavigationAction, NavigationPolicy, UrlMatcher, UrlPattern};
+use std::process::Command;
+use std::sync::{Arc, RwLock};
 
 /// The event is fired when [`OnNavigation`](crate::prelude::OnNavigation) returns `true`.
 #[derive(Clone, Debug, Event, Reflect)]
@@ -14,10 +20,246 @@ pub struct Navigated {
     pub uri: PassedUrl,
 }
 
+/// The event is fired when the navigation is canceled by [`NavigationPolicy`].
+#[derive(Clone, Debug, Event, Reflect)]
+pub struct NavigationBlocked {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// URL of the navigation destination
+    pub uri: PassedUrl,
+
+    /// Whether the url was opened in the system browser by [`NavigationAction::OpenExternally`].
+    ///
+    /// This is `false` if the scheme of the url is not `http`, `https` or `mailto`.
+    pub opened_externally: bool,
+}
+
 pub(crate) struct NavigationPlugin;
 
 impl Plugin for NavigationPlugin {
     fn build(&self, app: &mut App) {
-        app.register_wry_event::<Navigated>();
+        app
+            .register_wry_event::<Navigated>()
+            .register_wry_event::<NavigationBlocked>()
+            .init_resource::<NavigationPolicies>()
+            .add_systems(First, sync_navigation_policies);
+    }
+}
+
+/// The schemes of urls that [`NavigationAction::OpenExternally`] opens in the system browser.
+const EXTERNAL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// The compiled [`NavigationPolicy`] of each webview shared with the navigation handlers.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct NavigationPolicies(Arc<RwLock<HashMap<Entity, CompiledPolicy>>>);
+
+impl NavigationPolicies {
+    /// Returns the action applied to the navigation to `url`.
+    ///
+    /// If the webview has no [`NavigationPolicy`], the navigation is allowed.
+    pub(crate) fn evaluate(&self, webview_entity: Entity, url: &str) -> NavigationAction {
+        self.0
+            .read()
+            .ok()
+            .and_then(|policies| policies.get(&webview_entity).map(|policy| policy.evaluate(url)))
+            .unwrap_or(NavigationAction::Allow)
+    }
+}
+
+fn sync_navigation_policies(
+    policies: Res<NavigationPolicies>,
+    changed: Query<(Entity, &NavigationPolicy), Changed<NavigationPolicy>>,
+    mut removed: RemovedComponents<NavigationPolicy>,
+) {
+    let Ok(mut policies) = policies.0.write() else {
+        return;
+    };
+    for entity in removed.read() {
+        policies.remove(&entity);
+    }
+    for (entity, policy) in changed.iter() {
+        policies.insert(entity, CompiledPolicy::compile(policy));
+    }
+}
+
+struct CompiledPolicy {
+    rules: Vec<(NavigationAction, [Option<Matcher>; 3])>,
+    default_action: NavigationAction,
+}
+
+impl CompiledPolicy {
+    fn compile(policy: &NavigationPolicy) -> Self {
+        let rules = policy
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let UrlPattern { scheme, host, path } = &rule.url;
+                let matchers = [scheme, host, path].map(|matcher| Matcher::compile(matcher.as_ref()));
+                match matchers {
+                    [Ok(scheme), Ok(host), Ok(path)] => Some((rule.action, [scheme, host, path])),
+                    // Ignoring the invalid deny rule would allow the urls it is meant to deny.
+                    _ if rule.action == NavigationAction::Deny => {
+                        warn!("The navigation rule with the invalid regex denies all urls: {:?}", rule.url);
+                        Some((rule.action, [None, None, None]))
+                    }
+                    _ => {
+                        warn!("Ignored the navigation rule with the invalid regex: {:?}", rule.url);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self {
+            rules,
+            default_action: policy.default_action,
+        }
+    }
+
+    fn evaluate(&self, url: &str) -> NavigationAction {
+        let (scheme, host, path) = split_url(url);
+        let parts = [scheme.to_lowercase(), host.to_lowercase(), path.to_string()];
+        self.rules
+            .iter()
+            .find(|(_, matchers)| {
+                matchers
+                    .iter()
+                    .zip(parts.iter())
+                    .all(|(matcher, part)| matcher.as_ref().is_none_or(|matcher| matcher.is_match(part)))
+            })
+            .map_or(self.default_action, |(action, _)| *action)
+    }
+}
+
+enum Matcher {
+    Glob(String),
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    /// Compiles the pattern.
+    ///
+    /// Returns `Ok(None)` if `matcher` is `None`, which matches anything.
+    fn compile(matcher: Option<&UrlMatcher>) -> Result<Option<Self>, regex::Error> {
+        match matcher {
+            None => Ok(None),
+            Some(UrlMatcher::Glob(pattern)) => Ok(Some(Self::Glob(pattern.clone()))),
+            Some(UrlMatcher::Regex(pattern)) => regex::Regex::new(pattern).map(|regex| Some(Self::Regex(regex))),
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => glob_match(pattern, text),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Splits `url` into the scheme, the host and the path without the leading `/`.
+fn split_url(url: &str) -> (&str, &str, &str) {
+    let (scheme, rest) = url.split_once(':').unwrap_or(("", url));
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let Some(rest) = rest.strip_prefix("//") else {
+        return (scheme, "", rest.trim_start_matches('/'));
+    };
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    (scheme, host, path.trim_start_matches('/'))
+}
+
+/// Opens `url` in the system browser, and returns whether it was opened.
+///
+/// Urls whose schemes are not in [`EXTERNAL_SCHEMES`] are not passed to the system,
+/// since they could run local files or arbitrary protocol handlers.
+pub(crate) fn open_externally(url: &str) -> bool {
+    let (scheme, _, _) = split_url(url);
+    if !EXTERNAL_SCHEMES.iter().any(|external| external.eq_ignore_ascii_case(scheme)) {
+        warn!("Refused to open {url} externally because its scheme is not allowed");
+        return false;
+    }
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.args(["url.dll,FileProtocolHandler", url]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+    match command.spawn() {
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+            true
+        }
+        Err(e) => {
+            error!("Failed to open {url}: {e}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::handlers::navigation::{open_externally, split_url, CompiledPolicy};
+    use bevy_webview_core::prelude::{NavigationAction, NavigationPolicy, UrlMatcher, UrlPattern};
+
+    #[test]
+    fn split_into_parts() {
+        assert_eq!(split_url("https://user@Example.com:8080/store/items?id=1#top"), ("https", "Example.com", "store/items"));
+        assert_eq!(split_url("flurx://localhost/"), ("flurx", "localhost", ""));
+        assert_eq!(split_url("http://[::1]:3000/index.html"), ("http", "::1", "index.html"));
+        assert_eq!(split_url("about:blank"), ("about", "", "blank"));
+    }
+
+    #[test]
+    fn apply_first_matched_rule() {
+        let policy = CompiledPolicy::compile(&NavigationPolicy::default()
+            .allow(UrlPattern::default().scheme("flurx"))
+            .deny(UrlPattern::default().host("*.example.com").path("store/**"))
+            .open_externally(UrlPattern::default().scheme(UrlMatcher::regex("^https?$")))
+            .with_default_action(NavigationAction::Deny));
+        assert_eq!(policy.evaluate("flurx://localhost/index.html"), NavigationAction::Allow);
+        assert_eq!(policy.evaluate("https://shop.EXAMPLE.com/store/items"), NavigationAction::Deny);
+        assert_eq!(policy.evaluate("https://shop.example.com/news"), NavigationAction::OpenExternally);
+        assert_eq!(policy.evaluate("file:///etc/passwd"), NavigationAction::Deny);
+    }
+
+    #[test]
+    fn ignore_invalid_allow_regex() {
+        let policy = CompiledPolicy::compile(&NavigationPolicy::default()
+            .allow(UrlPattern::default().host(UrlMatcher::regex("(")))
+            .deny(UrlPattern::default().host("evil.com")));
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(policy.evaluate("https://example.com"), NavigationAction::Allow);
+    }
+
+    #[test]
+    fn invalid_deny_regex_denies_all() {
+        let policy = CompiledPolicy::compile(&NavigationPolicy::default()
+            .deny(UrlPattern::default().host(UrlMatcher::regex("(")))
+            .allow(UrlPattern::default().scheme("flurx")));
+        assert_eq!(policy.evaluate("flurx://localhost/index.html"), NavigationAction::Deny);
+        assert_eq!(policy.evaluate("https://example.com"), NavigationAction::Deny);
+    }
+
+    #[test]
+    fn refuse_to_open_disallowed_schemes() {
+        assert!(!open_externally("file:///etc/passwd"));
+        assert!(!open_externally("javascript:alert(1)"));
+        assert!(!open_externally("C:\\Windows\\System32\\calc.exe"));
     }
 }
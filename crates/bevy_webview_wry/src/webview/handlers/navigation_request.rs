@@ -0,0 +1,252 @@
+//! Decides navigations, new window requests and downloads in systems.
+
+use crate::prelude::{NavigationBlocked, PassedUrl, WebviewClosed, WryWebViews};
+use crate::util::WryResultLog;
+use crate::webview::handlers::{RegisterWryEvent, WryEvents};
+use bevy::prelude::{App, Entity, Event, EventReader, EventWriter, NonSend, Plugin, PreUpdate, Reflect, Res, Resource};
+use bevy::utils::HashSet;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Fired when the navigation, the new window request or the download is canceled to be decided in systems.
+///
+/// This is fired if [`OnNavigation::DEFERRED`](crate::prelude::OnNavigation::DEFERRED),
+/// [`OnNewWindowRequest::DEFERRED`](crate::prelude::OnNewWindowRequest::DEFERRED) or
+/// [`OnDownload::DEFERRED`](crate::prelude::OnDownload::DEFERRED) is specified.
+///
+/// Call [`NavigationRequested::respond`] to resume or discard the request.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct InMatch(bool);
+///
+/// fn decide_navigation(
+///     mut er: EventReader<NavigationRequested>,
+///     in_match: Res<InMatch>,
+/// ) {
+///     for request in er.read() {
+///         let is_store = request.url.0.contains("/store");
+///         request.respond(!(in_match.0 && is_store));
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Debug, Reflect)]
+pub struct NavigationRequested {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// The requested url.
+    pub url: PassedUrl,
+
+    /// The kind of the request.
+    pub kind: NavigationRequestKind,
+
+    #[reflect(ignore)]
+    responder: NavigationResponder,
+}
+
+impl NavigationRequested {
+    /// Resumes the request if `allow` is `true`, otherwise discards it.
+    ///
+    /// Only the first call takes effect.
+    ///
+    /// - [`NavigationRequestKind::Navigation`] and [`NavigationRequestKind::NewWindow`]: the webview loads the url.
+    /// - [`NavigationRequestKind::Download`]: the download is restarted.
+    pub fn respond(&self, allow: bool) {
+        if self.responder.responded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.responder.decisions.push(NavigationDecision {
+            webview_entity: self.webview_entity,
+            url: self.url.clone(),
+            kind: self.kind,
+            allow,
+        });
+    }
+}
+
+/// The kind of [`NavigationRequested`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Reflect)]
+pub enum NavigationRequestKind {
+    /// The webview is navigating to the url.
+    Navigation,
+
+    /// The webview requests to open the url in a new window.
+    NewWindow,
+
+    /// The webview starts downloading from the url.
+    Download,
+}
+
+#[derive(Default, Clone)]
+struct NavigationResponder {
+    decisions: WryEvents<NavigationDecision>,
+    responded: Arc<AtomicBool>,
+}
+
+impl Debug for NavigationResponder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NavigationResponder")
+            .field("responded", &self.responded.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+struct NavigationDecision {
+    webview_entity: Entity,
+    url: PassedUrl,
+    kind: NavigationRequestKind,
+    allow: bool,
+}
+
+/// Holds the requests waiting for the decision and the urls allowed by systems.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct DeferredNavigations {
+    requests: WryEvents<NavigationRequested>,
+    decisions: WryEvents<NavigationDecision>,
+    approved: Arc<Mutex<HashSet<(Entity, String, NavigationRequestKind)>>>,
+}
+
+impl DeferredNavigations {
+    /// Fires [`NavigationRequested`].
+    pub(crate) fn request(&self, webview_entity: Entity, url: String, kind: NavigationRequestKind) {
+        self.requests.push(NavigationRequested {
+            webview_entity,
+            url: PassedUrl(url),
+            kind,
+            responder: NavigationResponder {
+                decisions: self.decisions.clone(),
+                responded: Arc::default(),
+            },
+        });
+    }
+
+    /// Allows the next request of `kind` to `url` without firing [`NavigationRequested`].
+    ///
+    /// The approval expires when the webview starts loading another page; see [`DeferredNavigations::expire`].
+    pub(crate) fn approve(&self, webview_entity: Entity, url: String, kind: NavigationRequestKind) {
+        if let Ok(mut approved) = self.approved.lock() {
+            approved.insert((webview_entity, normalize_url(&url), kind));
+        }
+    }
+
+    /// Returns whether the request has been allowed by [`DeferredNavigations::approve`], and consumes the approval.
+    pub(crate) fn take_approval(&self, webview_entity: Entity, url: &str, kind: NavigationRequestKind) -> bool {
+        self.approved
+            .lock()
+            .is_ok_and(|mut approved| approved.remove(&(webview_entity, normalize_url(url), kind)))
+    }
+
+    /// Discards the approvals of the webview except for `loading_url`, which is called when the webview starts loading it.
+    ///
+    /// The approval of `loading_url` is kept because its download may not have started yet.
+    pub(crate) fn expire(&self, webview_entity: Entity, loading_url: &str) {
+        let loading_url = normalize_url(loading_url);
+        if let Ok(mut approved) = self.approved.lock() {
+            approved.retain(|(entity, url, _)| *entity != webview_entity || *url == loading_url);
+        }
+    }
+
+    /// Discards all approvals of the webview.
+    fn purge(&self, webview_entity: Entity) {
+        if let Ok(mut approved) = self.approved.lock() {
+            approved.retain(|(entity, _, _)| *entity != webview_entity);
+        }
+    }
+}
+
+/// Normalizes `url` so that equivalent urls such as `https://EXAMPLE.com:443` and `https://example.com/` are the same.
+///
+/// The url is returned as is if it can't be parsed.
+fn normalize_url(url: &str) -> String {
+    url::Url::parse(url).map_or_else(|_| url.to_string(), String::from)
+}
+
+pub(crate) struct NavigationRequestPlugin;
+
+impl Plugin for NavigationRequestPlugin {
+    fn build(&self, app: &mut App) {
+        let deferred = DeferredNavigations::default();
+        app
+            .register_type::<NavigationRequestKind>()
+            .register_wry_event::<NavigationRequested>()
+            .insert_resource(deferred.requests.clone())
+            .insert_resource(deferred)
+            .add_systems(PreUpdate, (
+                resume_navigations,
+                purge_closed_webviews,
+            ));
+    }
+}
+
+fn resume_navigations(
+    mut ew: EventWriter<NavigationBlocked>,
+    deferred: Res<DeferredNavigations>,
+    web_views: NonSend<WryWebViews>,
+) {
+    for NavigationDecision { webview_entity, url, kind, allow } in deferred.decisions.take_events() {
+        if !allow {
+            ew.send(NavigationBlocked {
+                webview_entity,
+                uri: url,
+                opened_externally: false,
+            });
+            continue;
+        }
+        let Some(webview) = web_views.get(&webview_entity) else {
+            continue;
+        };
+        deferred.approve(webview_entity, url.0.clone(), NavigationRequestKind::Navigation);
+        if kind == NavigationRequestKind::Download {
+            deferred.approve(webview_entity, url.0.clone(), NavigationRequestKind::Download);
+        }
+        webview.load_url(&url.0).output_log_if_failed();
+    }
+}
+
+fn purge_closed_webviews(
+    mut er: EventReader<WebviewClosed>,
+    deferred: Res<DeferredNavigations>,
+) {
+    for WebviewClosed { webview_entity } in er.read() {
+        deferred.purge(*webview_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::handlers::navigation_request::{DeferredNavigations, NavigationRequestKind};
+    use bevy::prelude::Entity;
+
+    #[test]
+    fn approve_equivalent_url() {
+        let deferred = DeferredNavigations::default();
+        deferred.approve(Entity::PLACEHOLDER, "https://EXAMPLE.com:443".to_string(), NavigationRequestKind::Navigation);
+        assert!(deferred.take_approval(Entity::PLACEHOLDER, "https://example.com/", NavigationRequestKind::Navigation));
+        assert!(!deferred.take_approval(Entity::PLACEHOLDER, "https://example.com/", NavigationRequestKind::Navigation));
+    }
+
+    #[test]
+    fn expire_on_other_page_load() {
+        let deferred = DeferredNavigations::default();
+        deferred.approve(Entity::PLACEHOLDER, "https://example.com/a".to_string(), NavigationRequestKind::Navigation);
+        deferred.approve(Entity::PLACEHOLDER, "https://example.com/b".to_string(), NavigationRequestKind::Download);
+        deferred.expire(Entity::PLACEHOLDER, "https://example.com/b");
+        assert!(!deferred.take_approval(Entity::PLACEHOLDER, "https://example.com/a", NavigationRequestKind::Navigation));
+        assert!(deferred.take_approval(Entity::PLACEHOLDER, "https://example.com/b", NavigationRequestKind::Download));
+    }
+
+    #[test]
+    fn purge_closed_webview() {
+        let deferred = DeferredNavigations::default();
+        deferred.approve(Entity::PLACEHOLDER, "https://example.com/".to_string(), NavigationRequestKind::NewWindow);
+        deferred.purge(Entity::PLACEHOLDER);
+        assert!(!deferred.take_approval(Entity::PLACEHOLDER, "https://example.com/", NavigationRequestKind::NewWindow));
+    }
+}
@@ -1,8 +1,10 @@
 //! Controls the process of creating the new window : [`wry::WebViewBuilder::with_new_window_req_handler`]
 
-use crate::prelude::{PassedUrl, Webview, WebviewUri};
+use crate::prelude::{LocalRoot, PassedUrl, Webview, WebviewUri};
 use crate::webview::handlers::{RegisterWryEvent, WryEvents};
-use bevy::prelude::{App, Commands, Entity, Event, EventWriter, Plugin, PreUpdate, Reflect, Res, Window};
+use bevy::prelude::{App, Commands, Component, Entity, Event, EventWriter, Plugin, PreUpdate, Query, Reflect, Res, Window, World};
+use bevy_flurx_ipc::prelude::IpcHandlers;
+use bevy_webview_core::prelude::*;
 
 /// The event indicating that a new window has been opened.
 ///
@@ -12,18 +14,18 @@ pub struct NewWindowOpened {
     /// The entity associated with the webview from which this event was fired.
     pub webview_entity: Entity,
 
-    /// The entity associated with the new [`Window`].
+    /// The entity associated with the new [`Window`],
+    /// or the new webview if it was created by [`NewWindowResponse::CreateEmbedded`].
     pub opened_window_entity: Entity,
 
     /// The url loaded in new [`Window`].
     pub url: PassedUrl,
 }
 
-#[derive(Event, Clone, Debug, Reflect)]
 pub(crate) struct NewWindowRequested {
     pub webview_entity: Entity,
     pub url: PassedUrl,
-    pub window: Window,
+    pub response: NewWindowResponse,
 }
 
 pub(crate) struct NewWindowRequestedPlugin;
@@ -31,8 +33,6 @@ pub(crate) struct NewWindowRequestedPlugin;
 impl Plugin for NewWindowRequestedPlugin {
     fn build(&self, app: &mut App) {
         app
-            .register_type::<NewWindowRequested>()
-            .add_event::<NewWindowRequested>()
             .init_resource::<WryEvents<NewWindowRequested>>()
             .register_wry_event::<NewWindowOpened>()
             .add_systems(PreUpdate, open_new_window);
@@ -43,14 +43,28 @@ fn open_new_window(
     mut commands: Commands,
     mut ew: EventWriter<NewWindowOpened>,
     events: Res<WryEvents<NewWindowRequested>>,
+    openers: Query<(Option<&EmbedWithin>, Option<&InheritOpenerConfig>)>,
 ) {
     for request in events.take_events() {
-        let opened_window_entity = commands
-            .spawn((
-                request.window,
-                Webview::Uri(WebviewUri(request.url.0.to_string())),
-            ))
-            .id();
+        let (embed_within, inherit) = openers.get(request.webview_entity).unwrap_or_default();
+        let webview = Webview::Uri(WebviewUri(request.url.0.to_string()));
+        let opened_window_entity = match request.response {
+            NewWindowResponse::CreateWindow(window) => commands.spawn((window, webview)).id(),
+            NewWindowResponse::CreateChildWindow(window) => {
+                let parent = embed_within.map_or(request.webview_entity, |embed_within| embed_within.0);
+                spawn_child_window(&mut commands, window, webview, parent)
+            }
+            NewWindowResponse::CreateEmbedded { parent, bounds } => commands
+                .spawn((webview, EmbedWithin(parent), bounds))
+                .id(),
+            NewWindowResponse::Allow | NewWindowResponse::Deny => continue,
+        };
+        if inherit.is_some_and(|inherit| inherit.0) {
+            let opener = request.webview_entity;
+            commands.queue(move |world: &mut World| {
+                inherit_opener_config(world, opener, opened_window_entity);
+            });
+        }
 
         ew.send(NewWindowOpened {
             webview_entity: request.webview_entity,
@@ -59,3 +73,47 @@ fn open_new_window(
         });
     }
 }
+
+#[cfg(feature = "child_window")]
+fn spawn_child_window(commands: &mut Commands, window: Window, webview: Webview, parent: Entity) -> Entity {
+    use bevy_child_window::prelude::ParentWindow;
+    commands.spawn((window, webview, ParentWindow(parent))).id()
+}
+
+#[cfg(not(feature = "child_window"))]
+fn spawn_child_window(commands: &mut Commands, window: Window, webview: Webview, _: Entity) -> Entity {
+    bevy::log::warn!("`NewWindowResponse::CreateChildWindow` requires the `child_window` feature; the window is created as a top-level window.");
+    commands.spawn((window, webview)).id()
+}
+
+fn inherit_opener_config(world: &mut World, opener: Entity, opened: Entity) {
+    fn inherit<C: Component + Clone>(world: &mut World, opener: Entity, opened: Entity) {
+        let Some(component) = world.get::<C>(opener).cloned() else {
+            return;
+        };
+        if let Ok(mut entity) = world.get_entity_mut(opened) {
+            entity.insert(component);
+        }
+    }
+    inherit::<UseDevtools>(world, opener, opened);
+    inherit::<AutoPlay>(world, opener, opened);
+    inherit::<EnableClipboard>(world, opener, opened);
+    inherit::<Background>(world, opener, opened);
+    inherit::<Incognito>(world, opener, opened);
+    inherit::<WebviewProfile>(world, opener, opened);
+    inherit::<WebviewProxy>(world, opener, opened);
+    inherit::<WebviewZoom>(world, opener, opened);
+    inherit::<HotkeysZoom>(world, opener, opened);
+    inherit::<UserAgent>(world, opener, opened);
+    inherit::<Theme>(world, opener, opened);
+    inherit::<BrowserAcceleratorKeys>(world, opener, opened);
+    inherit::<UseHttpsScheme>(world, opener, opened);
+    inherit::<ReloadOnCrash>(world, opener, opened);
+    inherit::<FallbackPage>(world, opener, opened);
+    inherit::<InitializationScripts>(world, opener, opened);
+    inherit::<Csp>(world, opener, opened);
+    inherit::<NavigationPolicy>(world, opener, opened);
+    inherit::<LocalRoot>(world, opener, opened);
+    inherit::<IpcHandlers>(world, opener, opened);
+    inherit::<InheritOpenerConfig>(world, opener, opened);
+}
@@ -1,8 +1,10 @@
 //! Controls page loading events.
 
-use crate::prelude::PassedUrl;
-use crate::webview::handlers::RegisterWryEvent;
-use bevy::prelude::{App, Entity, Event, Plugin, Reflect};
+use crate::prelude::{PassedUrl, WryWebViews};
+use crate::util::WryResultLog;
+use crate::webview::handlers::{RegisterWryEvent, WryEvents};
+use bevy::prelude::{App, Entity, Event, EventReader, EventWriter, NonSend, Plugin, PreUpdate, Query, Reflect, Res, Update};
+use bevy_webview_core::prelude::FallbackPage;
 
 /// Indicates that the content of the page has started loading
 #[derive(Event, Clone, Debug, Reflect)]
@@ -22,6 +24,35 @@ pub struct PageLoadFinished {
 
     /// The url of the loaded page.
     pub url: PassedUrl,
+
+    /// The HTTP status code of the response.
+    ///
+    /// This is `None` if the page was not loaded over HTTP.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Android / iOS**: Not supported; this is always `None`.
+    pub status: Option<u16>,
+}
+
+/// Indicates that the page failed to load, for example, because the network is unreachable.
+///
+/// Responses with error status codes such as `404` are not failures; see [`PageLoadFinished::status`].
+/// Loads cancelled by another navigation are not reported.
+///
+/// ## Platform-specific
+///
+/// - **Windows / macOS / Android / iOS**: Not supported; this event is never fired.
+#[derive(Event, Clone, Debug, Reflect)]
+pub struct PageLoadFailed {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// The url of the page that failed to load.
+    pub url: PassedUrl,
+
+    /// The description of the error.
+    pub reason: String,
 }
 
 pub(crate) struct PageLoadPlugin;
@@ -29,7 +60,106 @@ pub(crate) struct PageLoadPlugin;
 impl Plugin for PageLoadPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<PageLoadFinished>()
+            .add_event::<PageLoadFinished>()
+            .init_resource::<WryEvents<PageLoadFinished>>()
             .register_wry_event::<PageLoadStarted>()
-            .register_wry_event::<PageLoadFinished>();
+            .register_wry_event::<PageLoadFailed>()
+            .add_systems(PreUpdate, send_page_load_finished)
+            .add_systems(Update, show_fallback_page);
+    }
+}
+
+fn send_page_load_finished(
+    mut ew: EventWriter<PageLoadFinished>,
+    events: Res<WryEvents<PageLoadFinished>>,
+    web_views: NonSend<WryWebViews>,
+) {
+    for mut event in events.take_events() {
+        if let Some(webview) = web_views.0.get(&event.webview_entity) {
+            event.status = response_status(webview, &event.url.0);
+        }
+        ew.send(event);
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn response_status(webview: &wry::WebView, url: &str) -> Option<u16> {
+    use webkit2gtk::{URIResponseExt, WebResourceExt, WebViewExt};
+    use wry::WebViewExtUnix;
+
+    let response = webview.webview().main_resource()?.response()?;
+    // The main resource may already belong to the next navigation.
+    if response.uri().is_none_or(|uri| uri != url) {
+        return None;
+    }
+    match response.status_code() {
+        0 => None,
+        status => u16::try_from(status).ok(),
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn response_status(_: &wry::WebView, _: &str) -> Option<u16> {
+    None
+}
+
+fn show_fallback_page(
+    mut er: EventReader<PageLoadFailed>,
+    views: Query<&FallbackPage>,
+    web_views: NonSend<WryWebViews>,
+) {
+    for PageLoadFailed { webview_entity, url, .. } in er.read() {
+        let Ok(fallback) = views.get(*webview_entity) else {
+            continue;
+        };
+        let Some(webview) = web_views.0.get(webview_entity) else {
+            continue;
+        };
+        if !is_remote(&url.0) {
+            continue;
+        }
+        match fallback {
+            FallbackPage::Uri(uri) if uri.0 != url.0 => webview.load_url(&uri.0).output_log_if_failed(),
+            FallbackPage::Uri(_) => {}
+            FallbackPage::Html(html) => webview.load_html(html).output_log_if_failed(),
+        }
+    }
+}
+
+fn is_remote(url: &str) -> bool {
+    (url.starts_with("http://") || url.starts_with("https://"))
+        && !url.starts_with("http://flurx.")
+        && !url.starts_with("https://flurx.")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::handlers::page_load::is_remote;
+
+    #[test]
+    fn remote_urls() {
+        assert!(is_remote("https://example.com/news"));
+        assert!(is_remote("http://localhost:5173/"));
+    }
+
+    #[test]
+    fn local_urls() {
+        assert!(!is_remote("flurx://localhost/index.html"));
+        assert!(!is_remote("http://flurx.localhost/index.html"));
+        assert!(!is_remote("https://flurx.localhost/index.html"));
+        assert!(!is_remote("about:blank"));
     }
 }
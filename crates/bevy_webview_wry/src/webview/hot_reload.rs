@@ -0,0 +1,258 @@
+//! Reloads webviews when local resources are changed.
+
+use crate::prelude::{LocalRoot, WryWebViews};
+use crate::util::WryResultLog;
+use crate::webview::local_root::{resolve_mount, WebviewMounts};
+use crate::{WryAssetDir, WryLocalRoot};
+use bevy::asset::io::file::FileWatcher;
+use bevy::asset::io::AssetSourceEvent;
+use bevy::log::{error, warn};
+use bevy::prelude::{App, Commands, Entity, Event, EventWriter, NonSend, Plugin, Query, Reflect, Res, Resource, Startup, Update};
+use crossbeam_channel::Receiver;
+use std::path::Path;
+use std::time::Duration;
+
+/// Watches local resources and reloads the webviews displaying the changed resources.
+///
+/// This plugin requires the `hot_reload` feature, which enables bevy's `file_watcher`.
+///
+/// A webview is affected only if the changed file is located under the directory of its current page;
+/// for example, `flurx://localhost/hud/index.html` is reloaded when `hud/style.css` changes,
+/// but not when `editor/main.js` changes.
+///
+/// If all changed files are stylesheets, they are swapped without reloading the page.
+///
+/// Resources served from `LocalRoot::DevServer` or from asset sources
+/// not located on the file system are not watched.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin::default(),
+///         WebviewHotReloadPlugin::default(),
+///     ));
+/// ```
+pub struct WebviewHotReloadPlugin {
+    /// The time to wait for file events to settle before reloading.
+    ///
+    /// Default is `300ms`.
+    pub debounce: Duration,
+}
+
+impl Default for WebviewHotReloadPlugin {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(300),
+        }
+    }
+}
+
+impl Plugin for WebviewHotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        let debounce = self.debounce;
+        app
+            .register_type::<HotReloadKind>()
+            .register_type::<WebviewReloaded>()
+            .add_event::<WebviewReloaded>()
+            .add_systems(Startup, move |commands: Commands, asset_dir: Res<WryAssetDir>| {
+                start_watching(commands, asset_dir, debounce);
+            })
+            .add_systems(Update, reload_webviews);
+    }
+}
+
+/// Fired when the webview has been reloaded by [`WebviewHotReloadPlugin`].
+#[derive(Event, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct WebviewReloaded {
+    /// The entity associated with the reloaded webview.
+    pub webview_entity: Entity,
+
+    /// The paths of the changed resources relative to `flurx://localhost/`.
+    pub paths: Vec<String>,
+
+    /// How the changes were applied.
+    pub kind: HotReloadKind,
+}
+
+/// Represents how the changes were applied to the webview.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub enum HotReloadKind {
+    /// The page was reloaded.
+    Page,
+
+    /// Only the changed stylesheets were swapped.
+    Stylesheet,
+}
+
+#[derive(Resource)]
+struct AssetDirWatcher {
+    _watcher: FileWatcher,
+    receiver: Receiver<AssetSourceEvent>,
+}
+
+fn start_watching(
+    mut commands: Commands,
+    asset_dir: Res<WryAssetDir>,
+    debounce: Duration,
+) {
+    let Some(root) = asset_dir.0.as_ref() else {
+        warn!("Hot reload is disabled because the asset source is not located on the file system.");
+        return;
+    };
+    // `FileWatcher` strips the root from the absolute paths of the events, so the root must be absolute as well.
+    let root = match root.canonicalize() {
+        Ok(root) => root,
+        Err(e) => {
+            error!("Failed to watch {}: {e}", root.display());
+            return;
+        }
+    };
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    match FileWatcher::new(root.clone(), sender, debounce) {
+        Ok(watcher) => commands.insert_resource(AssetDirWatcher {
+            _watcher: watcher,
+            receiver,
+        }),
+        Err(e) => error!("Failed to watch {}: {e}", root.display()),
+    }
+}
+
+fn reload_webviews(
+    mut ew: EventWriter<WebviewReloaded>,
+    watcher: Option<Res<AssetDirWatcher>>,
+    web_views: NonSend<WryWebViews>,
+    local_roots: Query<&LocalRoot>,
+    default_root: Res<WryLocalRoot>,
+    mounts: Res<WebviewMounts>,
+) {
+    let Some(watcher) = watcher else {
+        return;
+    };
+    let changed = watcher
+        .receiver
+        .try_iter()
+        .flat_map(changed_paths)
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return;
+    }
+    for (webview_entity, webview) in web_views.iter() {
+        let Some(page) = webview.url().ok().as_deref().and_then(local_path).map(str::to_string) else {
+            continue;
+        };
+        let default_root = local_roots.get(*webview_entity).unwrap_or(&default_root.0);
+        let (mount, root, page) = resolve_mount(&mounts.0, default_root, &page);
+        #[cfg_attr(not(feature = "dev_server"), allow(irrefutable_let_patterns))]
+        let LocalRoot::Assets(root) = root else {
+            continue;
+        };
+        let paths = affected_paths(&as_url_path(root), mount, page, &changed);
+        if paths.is_empty() {
+            continue;
+        }
+        let kind = if paths.iter().all(|path| path.ends_with(".css")) {
+            webview.evaluate_script(&swap_stylesheets_script(&paths)).output_log_if_failed();
+            HotReloadKind::Stylesheet
+        } else {
+            webview.evaluate_script("location.reload()").output_log_if_failed();
+            HotReloadKind::Page
+        };
+        ew.send(WebviewReloaded {
+            webview_entity: *webview_entity,
+            paths,
+            kind,
+        });
+    }
+}
+
+fn changed_paths(event: AssetSourceEvent) -> Vec<String> {
+    match event {
+        AssetSourceEvent::AddedAsset(path) |
+        AssetSourceEvent::ModifiedAsset(path) |
+        AssetSourceEvent::RemovedAsset(path) => vec![as_url_path(&path)],
+        AssetSourceEvent::RenamedAsset { old, new } => vec![as_url_path(&old), as_url_path(&new)],
+        _ => Vec::new(),
+    }
+}
+
+fn as_url_path(path: &Path) -> String {
+    path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the path of the local resource displayed at `url`.
+fn local_path(url: &str) -> Option<&str> {
+    let path = ["flurx://localhost/", "http://flurx.localhost/", "https://flurx.localhost/"]
+        .iter()
+        .find_map(|prefix| url.strip_prefix(prefix))?;
+    Some(path.split(['?', '#']).next().unwrap_or_default())
+}
+
+/// Returns the changed paths located under the directory of `page`,
+/// converted into the paths relative to `flurx://localhost/`.
+///
+/// `changed` are relative to the asset directory and `page` is relative to `root`.
+fn affected_paths(root: &str, mount: &str, page: &str, changed: &[String]) -> Vec<String> {
+    let dir = page.rfind('/').map_or("", |i| &page[..=i]);
+    changed
+        .iter()
+        .filter_map(|path| {
+            if root.is_empty() {
+                Some(path.as_str())
+            } else {
+                path.strip_prefix(root)?.strip_prefix('/')
+            }
+        })
+        .filter(|path| path.starts_with(dir))
+        .map(|path| format!("{mount}{path}"))
+        .collect()
+}
+
+fn swap_stylesheets_script(paths: &[String]) -> String {
+    let paths = serde_json::to_string(paths).unwrap_or_else(|_| "[]".to_string());
+    format!(r#"(paths => {{
+    document.querySelectorAll('link[rel="stylesheet"]').forEach(link => {{
+        const url = new URL(link.href);
+        if (paths.includes(decodeURIComponent(url.pathname).slice(1))) {{
+            url.searchParams.set("flurx-reload", Date.now().toString());
+            link.href = url.href;
+        }}
+    }});
+}})({paths});"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::hot_reload::{affected_paths, local_path};
+
+    #[test]
+    fn extract_local_path() {
+        assert_eq!(local_path("flurx://localhost/hud/index.html?a=1#top"), Some("hud/index.html"));
+        assert_eq!(local_path("http://flurx.localhost/index.html"), Some("index.html"));
+        assert_eq!(local_path("https://example.com/index.html"), None);
+    }
+
+    #[test]
+    fn scope_by_page_directory() {
+        let changed = vec!["ui/hud/style.css".to_string(), "ui/editor/main.js".to_string()];
+        assert_eq!(affected_paths("ui", "", "hud/index.html", &changed), vec!["hud/style.css".to_string()]);
+        assert_eq!(affected_paths("ui", "", "index.html", &changed), vec!["hud/style.css".to_string(), "editor/main.js".to_string()]);
+        assert!(affected_paths("ui", "", "menu/index.html", &changed).is_empty());
+        assert!(affected_paths("other", "", "index.html", &changed).is_empty());
+    }
+
+    #[test]
+    fn map_to_mounted_path() {
+        let changed = vec!["editor_ui/css/main.css".to_string()];
+        assert_eq!(affected_paths("editor_ui", "editor/", "index.html", &changed), vec!["editor/css/main.css".to_string()]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Controls the lifecycle of the webview.
+
+use crate::prelude::WryWebViews;
+use crate::webview::handlers::RegisterWryEvent;
+use crate::webview::load_webview::WryProtocolHandlers;
+use bevy::prelude::{App, Commands, Entity, Event, EventWriter, NonSendMut, OnAdd, OnRemove, Plugin, Reflect, Res, Trigger};
+use bevy_webview_core::prelude::{Webview, WebviewInitialized};
+
+/// Fired when the webview has been created and attached to the entity.
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct WebviewCreated {
+    /// The entity associated with the created webview.
+    pub webview_entity: Entity,
+}
+
+/// Fired when the webview has been closed because the entity was despawned or [`Webview`] was removed.
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct WebviewClosed {
+    /// The entity associated with the closed webview.
+    pub webview_entity: Entity,
+}
+
+/// Fired when the web content process of the webview has terminated unexpectedly.
+///
+/// If [`ReloadOnCrash`](crate::prelude::ReloadOnCrash) is `true`, the page will be reloaded automatically.
+///
+/// This event is only fired on `Linux`.
+#[derive(Event, Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct WebviewProcessCrashed {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// The reason why the web content process was terminated.
+    pub reason: ProcessTerminationReason,
+}
+
+/// Represents the reason why the web content process was terminated.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub enum ProcessTerminationReason {
+    /// The web content process crashed.
+    Crashed,
+
+    /// The web content process exceeded the memory limit.
+    ExceededMemoryLimit,
+
+    /// The web content process was terminated by the api.
+    TerminatedByApi,
+
+    /// Unknown reason.
+    Unknown,
+}
+
+pub(crate) struct LifecyclePlugin;
+
+impl Plugin for LifecyclePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ProcessTerminationReason>()
+            .register_type::<WebviewCreated>()
+            .register_type::<WebviewClosed>()
+            .add_event::<WebviewCreated>()
+            .add_event::<WebviewClosed>()
+            .register_wry_event::<WebviewProcessCrashed>()
+            .add_observer(notify_created)
+            .add_observer(close_webview);
+    }
+}
+
+fn notify_created(
+    trigger: Trigger<OnAdd, WebviewInitialized>,
+    mut ew: EventWriter<WebviewCreated>,
+) {
+    ew.send(WebviewCreated {
+        webview_entity: trigger.entity(),
+    });
+}
+
+fn close_webview(
+    trigger: Trigger<OnRemove, Webview>,
+    mut commands: Commands,
+    mut ew: EventWriter<WebviewClosed>,
+    mut web_views: NonSendMut<WryWebViews>,
+    protocol_handlers: Res<WryProtocolHandlers>,
+) {
+    let webview_entity = trigger.entity();
+    if web_views.0.remove(&webview_entity).is_none() {
+        return;
+    }
+    protocol_handlers.remove(webview_entity);
+    commands.entity(webview_entity).remove::<WebviewInitialized>();
+    ew.send(WebviewClosed {
+        webview_entity,
+    });
+}
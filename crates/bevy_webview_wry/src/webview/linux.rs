@@ -1,12 +1,23 @@
-use crate::prelude::WryWebViews;
+use crate::prelude::{PageLoadFailed, PassedUrl, ProcessTerminationReason, ReloadOnCrash, WebviewInitialized, WebviewProcessCrashed, WryWebViews};
+use crate::webview::handlers::WryEvents;
+use crate::webview::zoom::{ReportedZoom, ReportedZoomEvent};
 use bevy::app::{App, First};
-use bevy::prelude::{NonSend, Plugin};
+use bevy::prelude::{Added, Entity, EventReader, NonSend, Plugin, Query, Res, Update};
+use webkit2gtk::{NetworkError, PolicyError, WebProcessTerminationReason, WebViewExt};
+use wry::WebViewExtUnix;
 
 pub struct WebviewSupportLinuxPlugin;
 
 impl Plugin for WebviewSupportLinuxPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(First, main_iteration_do);
+        app
+            .add_systems(First, main_iteration_do)
+            .add_systems(Update, (
+                watch_web_process_terminated,
+                watch_zoom_level,
+                watch_load_failed,
+                reload_crashed_webview,
+            ));
         gtk::init().expect("Failed to initialize GTK.");
     }
 }
@@ -17,3 +28,89 @@ fn main_iteration_do(
     gtk::main_iteration_do(false);
 }
 
+fn watch_web_process_terminated(
+    views: Query<Entity, Added<WebviewInitialized>>,
+    web_views: NonSend<WryWebViews>,
+    events: Res<WryEvents<WebviewProcessCrashed>>,
+) {
+    for webview_entity in views.iter() {
+        let Some(webview) = web_views.0.get(&webview_entity) else {
+            continue;
+        };
+        let events = events.clone();
+        webview.webview().connect_web_process_terminated(move |_, reason| {
+            events.push(WebviewProcessCrashed {
+                webview_entity,
+                reason: as_termination_reason(reason),
+            });
+        });
+    }
+}
+
+fn watch_zoom_level(
+    views: Query<Entity, Added<WebviewInitialized>>,
+    web_views: NonSend<WryWebViews>,
+    events: Res<WryEvents<ReportedZoomEvent>>,
+) {
+    for webview_entity in views.iter() {
+        let Some(webview) = web_views.0.get(&webview_entity) else {
+            continue;
+        };
+        let events = events.clone();
+        webview.webview().connect_zoom_level_notify(move |webview| {
+            events.push(ReportedZoomEvent {
+                webview_entity,
+                zoom: ReportedZoom::Zoom(webview.zoom_level()),
+            });
+        });
+    }
+}
+
+fn watch_load_failed(
+    views: Query<Entity, Added<WebviewInitialized>>,
+    web_views: NonSend<WryWebViews>,
+    events: Res<WryEvents<PageLoadFailed>>,
+) {
+    for webview_entity in views.iter() {
+        let Some(webview) = web_views.0.get(&webview_entity) else {
+            continue;
+        };
+        let events = events.clone();
+        webview.webview().connect_load_failed(move |_, _, url, error| {
+            // Cancelled by another navigation, a download or a navigation policy.
+            if error.matches(NetworkError::Cancelled) || error.matches(PolicyError::FrameLoadInterruptedByPolicyChange) {
+                return false;
+            }
+            events.push(PageLoadFailed {
+                webview_entity,
+                url: PassedUrl(url.to_string()),
+                reason: error.message().to_string(),
+            });
+            false
+        });
+    }
+}
+
+fn reload_crashed_webview(
+    mut er: EventReader<WebviewProcessCrashed>,
+    views: Query<&ReloadOnCrash>,
+    web_views: NonSend<WryWebViews>,
+) {
+    for event in er.read() {
+        if !views.get(event.webview_entity).is_ok_and(|reload| reload.0) {
+            continue;
+        }
+        if let Some(webview) = web_views.0.get(&event.webview_entity) {
+            webview.webview().reload();
+        }
+    }
+}
+
+fn as_termination_reason(reason: WebProcessTerminationReason) -> ProcessTerminationReason {
+    match reason {
+        WebProcessTerminationReason::Crashed => ProcessTerminationReason::Crashed,
+        WebProcessTerminationReason::ExceededMemoryLimit => ProcessTerminationReason::ExceededMemoryLimit,
+        WebProcessTerminationReason::TerminatedByApi => ProcessTerminationReason::TerminatedByApi,
+        _ => ProcessTerminationReason::Unknown,
+    }
+}
@@ -1,12 +1,14 @@
-use crate::prelude::{Csp, Webview};
+use crate::prelude::{Csp, LocalRoot, Webview};
 use crate::prelude::{InitializationScripts, WebviewInitialized};
 use crate::util::as_wry_rect;
+use crate::webview::dialog::WebviewDialogs;
 use crate::webview::handlers::{HandlerQueries, WryEventParams};
+use crate::webview::load_webview::csp::WebviewCsp;
 use crate::webview::load_webview::ipc::IpcHandlerParams;
-use crate::webview::load_webview::protocol::feed_uri;
+use crate::webview::load_webview::protocol::{webview_id, ProtocolParams};
+use crate::webview::profile::WryWebContexts;
 use crate::webview::WryWebViews;
-use crate::WryLocalRoot;
-use bevy::prelude::{App, Commands, Entity, Name, NonSend, NonSendMut, Or, Plugin, PreUpdate, Query, Res, Window, With, Without};
+use bevy::prelude::{App, Commands, Entity, Name, NonSend, NonSendMut, Or, Plugin, PreUpdate, Query, Res, Resource, Window, With, Without};
 use bevy::winit::WinitWindows;
 use bevy_webview_core::bundle::embedding::{Bounds, EmbedWithin};
 use bevy_webview_core::prelude::*;
@@ -14,16 +16,22 @@ use rand::distr::{Alphanumeric, SampleString};
 use std::ops::Deref;
 #[cfg(target_os = "macos")]
 use wry::WebViewExtMacOS;
-use wry::{WebView, WebViewBuilder};
+use wry::{ProxyConfig, ProxyEndpoint, WebContext, WebView, WebViewBuilder};
 
+mod csp;
 mod ipc;
 mod protocol;
 
+pub(crate) use protocol::WryProtocolHandlers;
+
 pub struct LoadWebviewPlugin;
 
 impl Plugin for LoadWebviewPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreUpdate, load_web_views);
+        app
+            .init_resource::<WryInitializationScripts>()
+            .init_resource::<WryProtocolHandlers>()
+            .add_systems(PreUpdate, load_web_views);
 
         #[cfg(target_os = "macos")]
         {
@@ -35,6 +43,12 @@ impl Plugin for LoadWebviewPlugin {
     }
 }
 
+/// The scripts injected into all webviews by plugins such as [`WebviewConsolePlugin`](crate::prelude::WebviewConsolePlugin).
+///
+/// These are executed after the built-in scripts and before [`InitializationScripts`].
+#[derive(Resource, Default)]
+pub(crate) struct WryInitializationScripts(pub Vec<String>);
+
 type Configs1<'a> = (
     &'a UseDevtools,
     &'a AutoPlay,
@@ -52,13 +66,15 @@ type Configs2<'a> = (
     &'a InitializationScripts,
     Option<&'a Csp>,
     Option<&'a Name>,
+    Option<&'a LocalRoot>,
+    Option<&'a WebviewDialogs>,
 );
 
 type ConfigsPlatformSpecific<'a> = (&'a Theme, &'a BrowserAcceleratorKeys, &'a UseHttpsScheme);
 
 fn load_web_views(
     mut commands: Commands,
-    mut web_views: NonSendMut<WryWebViews>,
+    (mut web_views, mut web_contexts): (NonSendMut<WryWebViews>, NonSendMut<WryWebContexts>),
     mut views: Query<
         (
             Entity,
@@ -68,6 +84,8 @@ fn load_web_views(
             ConfigsPlatformSpecific,
             Option<&EmbedWithin>,
             Option<&Bounds>,
+            Option<&WebviewProfile>,
+            Option<&WebviewProxy>,
         ),
         (
             Without<WebviewInitialized>,
@@ -76,24 +94,43 @@ fn load_web_views(
     >,
     ipc_params: IpcHandlerParams,
     event_params: WryEventParams,
-    local_root: Res<WryLocalRoot>,
+    (protocol_params, plugin_scripts): (ProtocolParams, Res<WryInitializationScripts>),
     windows: NonSend<WinitWindows>,
 ) {
-    for (webview_entity, handlers, configs1, configs2, configs_platform, embed_within, bounds) in
+    for (webview_entity, handlers, configs1, configs2, configs_platform, embed_within, bounds, profile, proxy) in
         views.iter_mut()
     {
-        let Some(builder) = new_builder(embed_within.is_some(), &bounds) else {
+        let id = webview_id(webview_entity);
+        let web_context = profile.map(|profile| web_contexts.get_or_create(profile));
+        let Some(builder) = new_builder(embed_within.is_some(), &bounds, web_context) else {
             continue;
         };
+        let mut builder = builder.with_id(&id);
+        if let Some(proxy) = proxy {
+            builder = builder.with_proxy_config(as_proxy_config(proxy));
+        }
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        let builder = match profile {
+            Some(profile) => {
+                use wry::WebViewBuilderExtDarwin;
+                builder.with_data_store_identifier(crate::webview::profile::data_store_identifier(&profile.0))
+            }
+            None => builder,
+        };
         let builder = ipc_params.feed_ipc(webview_entity, builder);
-        let builder = event_params.feed_handlers(webview_entity, handlers, builder);
+        let initial_url = match configs2.3 {
+            Webview::Uri(uri) => Some(uri.0.as_str()),
+            Webview::Html(_) => None,
+        };
+        let builder = event_params.feed_handlers(webview_entity, handlers, builder, initial_url);
         let builder = feed_configs1(builder, configs1);
         let builder = feed_configs2(
             builder,
             &mut commands,
             webview_entity,
             configs2,
-            &local_root,
+            &protocol_params,
+            &plugin_scripts,
             embed_within.is_some(),
         );
         let builder = feed_platform_configs(builder, configs_platform);
@@ -115,15 +152,31 @@ fn load_web_views(
     }
 }
 
-fn new_builder<'a>(has_parent: bool, bounds: &Option<&Bounds>) -> Option<WebViewBuilder<'a>> {
+fn new_builder<'a>(
+    has_parent: bool,
+    bounds: &Option<&Bounds>,
+    web_context: Option<&'a mut WebContext>,
+) -> Option<WebViewBuilder<'a>> {
+    let mut builder = match web_context {
+        Some(web_context) => WebViewBuilder::with_web_context(web_context),
+        None => WebViewBuilder::new(),
+    };
     if has_parent {
-        let mut builder = WebViewBuilder::new();
         if let Some(bounds) = bounds {
             builder = builder.with_bounds(as_wry_rect(bounds));
         }
-        Some(builder)
-    } else {
-        Some(WebViewBuilder::new())
+    }
+    Some(builder)
+}
+
+fn as_proxy_config(proxy: &WebviewProxy) -> ProxyConfig {
+    let endpoint = ProxyEndpoint {
+        host: proxy.host().to_string(),
+        port: proxy.port().to_string(),
+    };
+    match proxy {
+        WebviewProxy::Http { .. } => ProxyConfig::Http(endpoint),
+        WebviewProxy::Socks5 { .. } => ProxyConfig::Socks5(endpoint),
     }
 }
 
@@ -152,8 +205,9 @@ fn feed_configs2<'a>(
     builder: WebViewBuilder<'a>,
     commands: &mut Commands,
     entity: Entity,
-    (focused, hotkeys_zoom, user_agent, uri, initialization_scripts, csp, name): Configs2,
-    local_root: &WryLocalRoot,
+    (focused, hotkeys_zoom, user_agent, uri, initialization_scripts, csp, name, local_root, dialogs): Configs2,
+    protocol_params: &ProtocolParams,
+    plugin_scripts: &WryInitializationScripts,
     is_embedded: bool,
 ) -> WebViewBuilder<'a> {
     let identifier = if let Some(name) = name {
@@ -166,21 +220,24 @@ fn feed_configs2<'a>(
             .insert(Name::new(random_code.clone()));
         random_code
     };
+    let csp = csp.cloned().map(WebviewCsp::new);
     let mut builder = builder
         .with_focused(focused.0)
         .with_hotkeys_zoom(hotkeys_zoom.0)
-        .with_initialization_script(&initialization_script(initialization_scripts, &identifier, is_embedded));
+        .with_initialization_script(&initialization_script(initialization_scripts, plugin_scripts, &identifier, is_embedded, dialogs.is_some()));
     if let Some(user_agent) = user_agent.0.as_ref() {
         builder = builder.with_user_agent(user_agent);
     }
 
-    feed_uri(builder, uri, local_root, csp.cloned())
+    protocol_params.feed_uri(entity, builder, uri, csp, local_root)
 }
 
 fn initialization_script(
     initialization_scripts: &InitializationScripts,
+    plugin_scripts: &WryInitializationScripts,
     identifier: &str,
     is_embedded: bool,
+    dialogs: bool,
 ) -> String {
     let s1 = include_str!("../../scripts/windowIdentifier.js").replace("<WINDOW_IDENTIFIER>", identifier);
     let mut scripts = vec![
@@ -192,6 +249,10 @@ fn initialization_script(
         #[cfg(target_os = "linux")]
         scripts.push(include_str!("../../scripts/gripZoneOnLinux.js"));
     };
+    scripts.extend(plugin_scripts.0.iter().map(String::as_str));
+    if dialogs {
+        scripts.push(include_str!("../../scripts/dialog.js"));
+    }
     let s2 = initialization_scripts.to_scripts();
     scripts.push(&s2);
     scripts.join(";")
@@ -0,0 +1,200 @@
+use crate::prelude::{Csp, ProtocolResponse};
+use crate::util::glob_match;
+use rand::distr::{Alphanumeric, SampleString};
+use wry::http::header::{CACHE_CONTROL, CONTENT_LENGTH, CONTENT_SECURITY_POLICY, CONTENT_TYPE, ETAG, LAST_MODIFIED};
+use wry::http::{HeaderValue, StatusCode};
+
+/// Directives which are ignored when delivered via the `<meta>` tag.
+const HEADER_ONLY_DIRECTIVES: [&str; 4] = ["frame-ancestors", "report-uri", "report-to", "sandbox"];
+
+/// [`Csp`] of the webview.
+#[derive(Clone)]
+pub(crate) struct WebviewCsp {
+    csp: Csp,
+}
+
+impl WebviewCsp {
+    pub(crate) fn new(csp: Csp) -> Self {
+        Self {
+            csp,
+        }
+    }
+
+    /// Generates the nonce for a new document if [`Csp::nonce`] is enabled.
+    pub(crate) fn generate_nonce(&self) -> Option<String> {
+        self
+            .csp
+            .uses_nonce()
+            .then(|| Alphanumeric.sample_string(&mut rand::rng(), 32))
+    }
+
+    /// Returns the policy applied to the resource located at `path` relative to `flurx://localhost/`.
+    pub(crate) fn policy(&self, path: &str, nonce: Option<&str>) -> String {
+        let path = path.trim_start_matches('/');
+        match self.csp.overrides().iter().find(|o| glob_match(&o.path, path)) {
+            Some(o) => self.csp.with_directives(&o.directives).to_policy(nonce),
+            None => self.csp.to_policy(nonce),
+        }
+    }
+
+    /// Sets the policy to the response of the resource located at `path`.
+    ///
+    /// If the response is an HTML document, a new nonce is generated for it and exposed by [`expose_nonce`].
+    /// Since the nonce changes on every load, such documents are not cached.
+    pub(crate) fn apply_to_response(&self, path: &str, mut response: ProtocolResponse) -> ProtocolResponse {
+        let is_document = response.status() == StatusCode::OK && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("text/html"));
+        let nonce = is_document.then(|| self.generate_nonce()).flatten();
+        if let Some(nonce) = nonce.as_deref() {
+            if let Ok(html) = std::str::from_utf8(response.body()) {
+                *response.body_mut() = expose_nonce(html, nonce).into_bytes();
+            }
+            let len = response.body().len();
+            let headers = response.headers_mut();
+            headers.remove(ETAG);
+            headers.remove(LAST_MODIFIED);
+            headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+            if len == 0 {
+                // The body of `HEAD` is empty, so the length of the document is unknown.
+                headers.remove(CONTENT_LENGTH);
+            } else {
+                headers.insert(CONTENT_LENGTH, HeaderValue::from(len));
+            }
+        }
+        if let Ok(policy) = HeaderValue::from_str(&self.policy(path, nonce.as_deref())) {
+            response.headers_mut().insert(CONTENT_SECURITY_POLICY, policy);
+        }
+        response
+    }
+
+    /// Inserts the policy into `html` as the `<meta http-equiv="Content-Security-Policy">` tag.
+    ///
+    /// If the nonce is enabled, it is generated for this document and exposed by [`expose_nonce`].
+    pub(crate) fn apply_to_html(&self, html: &str) -> String {
+        let nonce = self.generate_nonce();
+        let csp = self
+            .csp
+            .directives()
+            .iter()
+            .filter(|directive| !HEADER_ONLY_DIRECTIVES.contains(&directive.name.as_str()))
+            .fold(Csp::default(), |csp, directive| csp.directive(&directive.name, directive.sources.iter().cloned()));
+        let meta = format!(
+            r#"<meta http-equiv="Content-Security-Policy" content="{}">"#,
+            csp.to_policy(nonce.as_deref()).replace('&', "&amp;").replace('"', "&quot;"),
+        );
+        match nonce {
+            Some(nonce) => insert_into_head(&expose_nonce(html, &nonce), &meta),
+            None => insert_into_head(html, &meta),
+        }
+    }
+}
+
+/// Inserts the script that exposes `nonce` as `window.__FLURX__.cspNonce` into the document.
+///
+/// The nonce is passed only to documents served by this crate, so pages of other origins can't read it.
+pub(crate) fn expose_nonce(html: &str, nonce: &str) -> String {
+    let script = format!(
+        r#"<script nonce="{nonce}">Object.defineProperty(window.__FLURX__,"cspNonce",{{value:"{nonce}",writable:false,configurable:true}});</script>"#,
+    );
+    insert_into_head(html, &script)
+}
+
+fn insert_into_head(html: &str, tag: &str) -> String {
+    let at = head_start(html).unwrap_or(0);
+    format!("{}{tag}{}", &html[..at], &html[at..])
+}
+
+/// Returns the position just after the opening tag of `<head>` or `<html>`.
+fn head_start(html: &str) -> Option<usize> {
+    let lower = html.to_ascii_lowercase();
+    ["<head", "<html"].iter().find_map(|tag| {
+        let start = lower
+            .match_indices(tag)
+            .map(|(i, _)| i)
+            .find(|i| lower[i + tag.len()..].starts_with(['>', ' ', '\t', '\r', '\n']))?;
+        Some(start + lower[start..].find('>')? + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Csp;
+    use crate::webview::load_webview::csp::{expose_nonce, WebviewCsp};
+    use wry::http::header::{CONTENT_LENGTH, CONTENT_SECURITY_POLICY, CONTENT_TYPE, ETAG};
+    use wry::http::Response;
+
+    #[test]
+    fn apply_path_override() {
+        let csp = WebviewCsp::new(Csp::default()
+            .default_src(["'self'"])
+            .override_path("editor/**", Csp::default().script_src(["'unsafe-eval'"])));
+        assert_eq!(csp.policy("/index.html", None), "default-src 'self'");
+        assert_eq!(csp.policy("/editor/js/main.js", None), "default-src 'self'; script-src 'unsafe-eval'");
+    }
+
+    #[test]
+    fn generate_nonce_per_document() {
+        let csp = WebviewCsp::new(Csp::default().script_src(["'self'"]).nonce(true));
+        let nonce = csp.generate_nonce().unwrap();
+        assert_ne!(csp.generate_nonce().unwrap(), nonce);
+        assert!(csp.policy("index.html", Some(&nonce)).contains(&format!("'nonce-{nonce}'")));
+        assert!(WebviewCsp::new(Csp::default()).generate_nonce().is_none());
+    }
+
+    #[test]
+    fn apply_nonce_to_document_response() {
+        let csp = WebviewCsp::new(Csp::default().script_src(["'self'"]).nonce(true));
+        let response = Response::builder()
+            .header(CONTENT_TYPE, "text/html")
+            .header(ETAG, "\"a\"")
+            .body(b"<html><head></head></html>".to_vec())
+            .unwrap();
+        let response = csp.apply_to_response("/index.html", response);
+        let policy = response.headers()[CONTENT_SECURITY_POLICY].to_str().unwrap();
+        let nonce = policy.split("'nonce-").nth(1).unwrap().split('\'').next().unwrap();
+        let body = String::from_utf8(response.body().clone()).unwrap();
+        assert!(body.contains(&format!(r#"<script nonce="{nonce}">"#)));
+        assert_eq!(response.headers()[CONTENT_LENGTH], body.len().to_string());
+        assert!(response.headers().get(ETAG).is_none());
+    }
+
+    #[test]
+    fn no_nonce_for_subresources() {
+        let csp = WebviewCsp::new(Csp::default().script_src(["'self'"]).nonce(true));
+        let response = Response::builder()
+            .header(CONTENT_TYPE, "text/javascript")
+            .body(b"console.log(1)".to_vec())
+            .unwrap();
+        let response = csp.apply_to_response("/main.js", response);
+        assert_eq!(response.headers()[CONTENT_SECURITY_POLICY], "script-src 'self'");
+        assert_eq!(response.body(), b"console.log(1)");
+    }
+
+    #[test]
+    fn expose_nonce_to_document() {
+        assert_eq!(
+            expose_nonce("<html><head></head></html>", "abc"),
+            r#"<html><head><script nonce="abc">Object.defineProperty(window.__FLURX__,"cspNonce",{value:"abc",writable:false,configurable:true});</script></head></html>"#,
+        );
+    }
+
+    #[test]
+    fn insert_meta_into_head() {
+        let csp = WebviewCsp::new(Csp::from("default-src 'self'; frame-ancestors 'none'"));
+        assert_eq!(
+            csp.apply_to_html("<HTML><Head lang=\"en\"><title>a</title></head></html>"),
+            "<HTML><Head lang=\"en\"><meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'self'\"><title>a</title></head></html>",
+        );
+        assert_eq!(
+            csp.apply_to_html("<html><header></header></html>"),
+            "<html><meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'self'\"><header></header></html>",
+        );
+        assert_eq!(
+            csp.apply_to_html("<h1>Hello</h1>"),
+            "<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'self'\"><h1>Hello</h1>",
+        );
+    }
+}
@@ -1,183 +1,226 @@
-use std::path::PathBuf;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{AssetServer, Entity, Res, Resource};
+use bevy::utils::HashMap;
+use bevy::tasks::{IoTaskPool, TaskPool};
+use bevy_flurx::prelude::Map;
+use bevy_flurx_ipc::prelude::WebviewEntity;
+use std::sync::{Arc, RwLock};
+use wry::http::{HeaderMap, StatusCode};
+use wry::{RequestAsyncResponder, WebViewBuilder};
 
-use wry::http::header::{CONTENT_SECURITY_POLICY, CONTENT_TYPE};
-use wry::http::Response;
-use wry::{http, WebViewBuilder};
+use crate::prelude::{LocalRoot, ProtocolAccess, ProtocolAccessDenied, ProtocolHeaders, ProtocolRequest, Webview};
+use crate::webview::custom_protocol::{PendingProtocolRequest, WebviewProtocols};
+use crate::webview::handlers::WryEvents;
+use crate::webview::load_webview::csp::WebviewCsp;
+use crate::webview::load_webview::protocol::local_resource::{get_response, status_response, LocalResourceReader};
+use crate::webview::local_root::{resolve_mount, WebviewMounts};
+use crate::webview::protocol_headers::with_headers;
+use crate::webview::route::{RouteRequest, WebviewRoutes};
+use crate::{WryAssetDir, WryAssetSource, WryLocalRoot};
 
-use crate::prelude::{Csp, Webview};
-use crate::WryLocalRoot;
+#[cfg(feature = "dev_server")]
+mod dev_server;
+mod local_resource;
+mod mime;
 
-pub fn feed_uri<'a>(
-    builder: WebViewBuilder<'a>,
-    webview: &Webview,
-    local_root: &WryLocalRoot,
-    csp: Option<Csp>,
-) -> WebViewBuilder<'a> {
-    let builder = match webview {
-        Webview::Uri(uri) => builder.with_url(&uri.0),
-        Webview::Html(html) => builder.with_html(html),
-    };
-    feed_custom_protocol(builder, local_root.clone(), csp)
+#[derive(SystemParam)]
+pub(crate) struct ProtocolParams<'w> {
+    local_root: Res<'w, WryLocalRoot>,
+    mounts: Res<'w, WebviewMounts>,
+    asset_source: Res<'w, WryAssetSource>,
+    asset_server: Option<Res<'w, AssetServer>>,
+    asset_dir: Res<'w, WryAssetDir>,
+    access: Res<'w, ProtocolAccess>,
+    access_denied: Res<'w, WryEvents<ProtocolAccessDenied>>,
+    headers: Res<'w, ProtocolHeaders>,
+    protocols: Res<'w, WebviewProtocols>,
+    routes: Res<'w, WebviewRoutes>,
+    pending_requests: Res<'w, WryEvents<PendingProtocolRequest>>,
+    handlers: Res<'w, WryProtocolHandlers>,
 }
 
-fn feed_custom_protocol(
-    builder: WebViewBuilder,
-    local_root: WryLocalRoot,
-    csp: Option<Csp>,
-) -> WebViewBuilder {
-    let local_root = local_root.0;
-    builder.with_custom_protocol("flurx".to_string(), move |_, request| {
-        match get_response(request, &local_root, &csp) {
-            Ok(r) => r.map(Into::into),
-            Err(e) => http::Response::builder()
-                .header(CONTENT_TYPE, "text/plain")
-                .status(500)
-                .body(e.to_string().as_bytes().to_vec())
-                .unwrap()
-                .map(Into::into),
+type WebviewProtocolHandler = Arc<dyn Fn(ProtocolRequest, RequestAsyncResponder) + Send + Sync>;
+
+/// The custom protocol handlers of each webview, looked up by the id of the webview.
+///
+/// On Linux, custom protocols are registered per `WebContext`, so webviews sharing a
+/// [`WebviewProfile`](crate::prelude::WebviewProfile) would otherwise share the handler of the last created webview.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct WryProtocolHandlers(Arc<RwLock<HashMap<String, HashMap<String, WebviewProtocolHandler>>>>);
+
+impl WryProtocolHandlers {
+    /// Removes the handlers of the webview.
+    pub(crate) fn remove(&self, webview_entity: Entity) {
+        if let Ok(mut handlers) = self.0.write() {
+            handlers.remove(&webview_id(webview_entity));
+        }
+    }
+
+    fn register<'a>(
+        &self,
+        builder: WebViewBuilder<'a>,
+        webview_entity: Entity,
+        scheme: String,
+        handler: impl Fn(ProtocolRequest, RequestAsyncResponder) + Send + Sync + 'static,
+    ) -> WebViewBuilder<'a> {
+        if let Ok(mut handlers) = self.0.write() {
+            handlers
+                .entry(webview_id(webview_entity))
+                .or_default()
+                .insert(scheme.clone(), Arc::new(handler));
         }
-    })
+        let handlers = self.clone();
+        builder.with_asynchronous_custom_protocol(scheme.clone(), move |id, request, responder| {
+            let handler = handlers
+                .0
+                .read()
+                .ok()
+                .and_then(|handlers| handlers.get(id)?.get(&scheme).cloned());
+            match handler {
+                Some(handler) => handler(request, responder),
+                None => responder.respond(status_response(StatusCode::NOT_FOUND, "")),
+            }
+        })
+    }
 }
 
-fn get_response(
-    request: http::Request<Vec<u8>>,
-    local_root: &PathBuf,
-    csp: &Option<Csp>,
-) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error>> {
-    let path = request.uri().path();
-    let root = PathBuf::from("assets").join(local_root);
-    let path = if path == "/" {
-        "index.html"
-    } else {
-        &path[1..]
-    };
-    let content = std::fs::read(std::fs::canonicalize(root.join(path))?)?;
-    let mimetype = if path.ends_with(".htm") || path.ends_with(".html") || path == "/" {
-        "text/html"
-    } else if path.ends_with(".txt") {
-        "text/plain"
-    } else if path.ends_with(".css") {
-        "text/css"
-    } else if path.ends_with(".csv") {
-        "text/csv"
-    } else if path.ends_with(".js") {
-        "text/javascript"
-    } else if path.ends_with(".jpeg") {
-        "image/jpeg"
-    } else if path.ends_with(".png") {
-        "image/png"
-    } else if path.ends_with(".gif") {
-        "image/gif"
-    } else if path.ends_with(".bmp") {
-        "image/bmp"
-    } else if path.ends_with(".svg") {
-        "image/svg+xml"
-    } else if path.ends_with(".json") {
-        "application/json"
-    } else if path.ends_with(".pdf") {
-        "application/pdf"
-    } else if path.ends_with(".zip") {
-        "application/zip"
-    } else if path.ends_with(".lzh") {
-        "application/x-lzh"
-    } else if path.ends_with(".tar") {
-        "application/x-tar"
-    } else if path.ends_with(".wasm") {
-        "application/wasm"
-    } else if path.ends_with(".mp3") {
-        "audio/mp3g"
-    } else if path.ends_with(".mp4") {
-        "video/mp4"
-    } else if path.ends_with(".mpeg") {
-        "video/mpeg"
-    } else if path.ends_with(".aac") {
-        "audio/aac"
-    } else if path.ends_with(".abw") {
-        "application/x-abiword"
-    } else if path.ends_with(".arc") {
-        "application/x-freearc"
-    } else if path.ends_with(".avi") {
-        "video/m-msvideo"
-    } else if path.ends_with(".azw") {
-        "application/vnd.amazon.ebook"
-    } else if path.ends_with(".bin") {
-        "application/octet-stream"
-    } else if path.ends_with(".bz") {
-        "application/x-bzip"
-    } else if path.ends_with("bz2") {
-        "application/x-bzip2"
-    } else if path.ends_with(".csh") {
-        "application/x-csh"
-    } else if path.ends_with(".doc") {
-        "application/msword"
-    } else if path.ends_with(".docx") {
-        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
-    } else if path.ends_with(".eot") {
-        "application/vnd.ms-fontobject"
-    } else if path.ends_with(".epub") {
-        "application/epub+zip"
-    } else if path.ends_with(".gz") {
-        "application/gzip"
-    } else if path.ends_with(".ico") {
-        "image/vnd.microsoft.icon"
-    } else if path.ends_with(".ics") {
-        "text/calendar"
-    } else if path.ends_with(".jar") {
-        "application/java-archive"
-    } else if path.ends_with(".jpeg") || path.ends_with(".jpg") {
-        "image/jpeg"
-    } else if path.ends_with(".mid") || path.ends_with(".midi") {
-        "audio/midi"
-    } else if path.ends_with(".mpkg") {
-        "application/vnd.apple.installer+xml"
-    } else if path.ends_with(".odp") {
-        "application/vnd.oasis.opendocument.presentation"
-    } else if path.ends_with(".ods") {
-        "application/vnd.oasis.opendocument.spreadsheet"
-    } else if path.ends_with(".odt") {
-        "application/vnd.oasis.opendocument.text"
-    } else if path.ends_with(".oga") {
-        "audio/ogg"
-    } else if path.ends_with(".ogv") {
-        "video/ogg"
-    } else if path.ends_with(".ogx") {
-        "application/ogg"
-    } else if path.ends_with(".otf") {
-        "font/otf"
-    } else if path.ends_with(".ppt") {
-        "application/vnd.ms-powerpoint"
-    } else if path.ends_with(".pptx") {
-        "application/vnd.openxmlformats-officedocument.presentationml.presentation"
-    } else if path.ends_with(".rar") {
-        "application/vnd.rar"
-    } else if path.ends_with(".rtf") {
-        "application/rtf"
-    } else if path.ends_with(".sh") {
-        "application/x-sh"
-    } else if path.ends_with(".swf") {
-        "application/x-shockwave-flash"
-    } else if path.ends_with(".tif") || path.ends_with(".tiff") {
-        "image/tiff"
-    } else if path.ends_with(".ttf") {
-        "font/ttf"
-    } else if path.ends_with(".vsd") {
-        "application/vnd.visio"
-    } else if path.ends_with(".wav") {
-        "audio/wav"
-    } else if path.ends_with(".weba") {
-        "audio/webm"
-    } else if path.ends_with(".webm") {
-        "video/web"
-    } else {
-        panic!("not implemented content type {path}");
-    };
+/// Returns the id of the webview passed to [`WebViewBuilder::with_id`].
+pub(crate) fn webview_id(webview_entity: Entity) -> String {
+    webview_entity.to_bits().to_string()
+}
+
+impl ProtocolParams<'_> {
+    pub(crate) fn feed_uri<'a>(
+        &self,
+        webview_entity: Entity,
+        builder: WebViewBuilder<'a>,
+        webview: &Webview,
+        csp: Option<WebviewCsp>,
+        local_root: Option<&LocalRoot>,
+    ) -> WebViewBuilder<'a> {
+        let builder = match (webview, csp.as_ref()) {
+            (Webview::Uri(uri), _) => builder.with_url(&uri.0),
+            (Webview::Html(html), Some(csp)) => builder.with_html(csp.apply_to_html(html)),
+            (Webview::Html(html), None) => builder.with_html(html),
+        };
+        let builder = self.feed_flurx_protocol(webview_entity, builder, csp, local_root);
+        self.feed_user_protocols(webview_entity, builder)
+    }
+
+    fn feed_flurx_protocol<'a>(
+        &self,
+        webview_entity: Entity,
+        builder: WebViewBuilder<'a>,
+        csp: Option<WebviewCsp>,
+        local_root: Option<&LocalRoot>,
+    ) -> WebViewBuilder<'a> {
+        let local_resources = self.local_resources(webview_entity, local_root.unwrap_or(&self.local_root.0));
+        let mounts = self
+            .mounts
+            .0
+            .iter()
+            .map(|(name, root)| (name.clone(), self.local_resources(webview_entity, root)))
+            .collect::<HashMap<_, _>>();
+        let routes = self.routes.clone();
+        let pending_requests = self.pending_requests.clone();
+        let protocol_headers = self.headers.clone();
+        self.handlers.register(builder, webview_entity, "flurx".to_string(), move |request, responder| {
+            let headers = protocol_headers.response_headers(&request);
+            if protocol_headers.is_preflight(&request) {
+                responder.respond(with_headers(status_response(StatusCode::NO_CONTENT, ""), &headers));
+                return;
+            }
+            if let Some((handler, params)) = routes.find(request.method(), request.uri().path()) {
+                let action = handler
+                    .seed()
+                    .with(RouteRequest {
+                        webview_entity,
+                        params,
+                        request,
+                    })
+                    .map(move |response| with_headers(response, &headers));
+                pending_requests.push(PendingProtocolRequest::new(action, responder));
+                return;
+            }
+            let request_path = request.uri().path().to_string();
+            let (_, local_resources, path) = resolve_mount(&mounts, &local_resources, &request_path);
+            local_resources.respond(request, path.to_string(), responder, csp.clone(), headers);
+        })
+    }
 
-    let mut response_builder = Response::builder();
-    if let Some(csp) = csp {
-        response_builder = response_builder.header(CONTENT_SECURITY_POLICY, csp.0.as_str());
+    fn local_resources(&self, webview_entity: Entity, local_root: &LocalRoot) -> LocalResources {
+        match local_root {
+            LocalRoot::Assets(root) => LocalResources::Assets(LocalResourceReader {
+                webview_entity,
+                asset_server: self.asset_server.as_deref().cloned(),
+                source: self.asset_source.0.clone(),
+                root: root.clone(),
+                disk_root: self.asset_dir.0.as_ref().map(|dir| dir.join(root)),
+                access: self.access.clone(),
+                access_denied: self.access_denied.clone(),
+            }),
+            #[cfg(feature = "dev_server")]
+            LocalRoot::DevServer(url) => LocalResources::DevServer(dev_server::DevServerProxy::new(url)),
+        }
+    }
+
+    fn feed_user_protocols<'a>(
+        &self,
+        webview_entity: Entity,
+        mut builder: WebViewBuilder<'a>,
+    ) -> WebViewBuilder<'a> {
+        for (scheme, handler) in self.protocols.0.iter() {
+            let handler = handler.clone();
+            let pending_requests = self.pending_requests.clone();
+            builder = self.handlers.register(builder, webview_entity, scheme.clone(), move |mut request, responder| {
+                request.extensions_mut().insert(WebviewEntity(webview_entity));
+                pending_requests.handle(&handler, request, responder);
+            });
+        }
+        builder
+    }
+}
+
+enum LocalResources {
+    Assets(LocalResourceReader),
+    #[cfg(feature = "dev_server")]
+    DevServer(dev_server::DevServerProxy),
+}
+
+impl LocalResources {
+    /// Responds with the resource located at `path` relative to the root.
+    fn respond(
+        &self,
+        request: ProtocolRequest,
+        path: String,
+        responder: RequestAsyncResponder,
+        csp: Option<WebviewCsp>,
+        headers: HeaderMap,
+    ) {
+        let request_path = request.uri().path().to_string();
+        let apply_csp = move |response| match csp.as_ref() {
+            Some(csp) => csp.apply_to_response(&request_path, response),
+            None => response,
+        };
+        match self {
+            Self::Assets(reader) => {
+                let reader = reader.clone();
+                IoTaskPool::get_or_init(TaskPool::default)
+                    .spawn(async move {
+                        let response = get_response(&request, &path, &reader).await;
+                        responder.respond(with_headers(apply_csp(response), &headers));
+                    })
+                    .detach();
+            }
+            #[cfg(feature = "dev_server")]
+            Self::DevServer(proxy) => {
+                let proxy = proxy.clone();
+                // The proxy blocks until the dev server responds, so it must not occupy the IO task pool.
+                blocking::unblock(move || {
+                    responder.respond(with_headers(apply_csp(proxy.proxy(request, &path)), &headers));
+                })
+                .detach();
+            }
+        }
     }
-    response_builder
-        .header(CONTENT_TYPE, mimetype)
-        .body(content)
-        .map_err(Into::into)
 }
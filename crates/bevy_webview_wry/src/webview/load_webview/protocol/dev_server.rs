@@ -0,0 +1,55 @@
+use crate::prelude::{ProtocolRequest, ProtocolResponse};
+use crate::webview::load_webview::protocol::local_resource::{build, status_response};
+use wry::http::header::{ACCEPT_ENCODING, CONTENT_LENGTH, HOST, TRANSFER_ENCODING};
+use wry::http::{Response, StatusCode};
+
+/// Proxies requests to the `flurx` protocol to [`LocalRoot::DevServer`](crate::prelude::LocalRoot::DevServer).
+#[derive(Clone)]
+pub(crate) struct DevServerProxy {
+    client: reqwest::blocking::Client,
+    url: String,
+}
+
+impl DevServerProxy {
+    pub(crate) fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::blocking::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Forwards the request to `path` of the dev server.
+    ///
+    /// This blocks the current thread until the dev server responds.
+    pub(crate) fn proxy(&self, request: ProtocolRequest, path: &str) -> ProtocolResponse {
+        self
+            .send(request, path)
+            .unwrap_or_else(|e| status_response(StatusCode::BAD_GATEWAY, &e.to_string()))
+    }
+
+    fn send(&self, request: ProtocolRequest, path: &str) -> reqwest::Result<ProtocolResponse> {
+        let url = match request.uri().query() {
+            Some(query) => format!("{}/{path}?{query}", self.url),
+            None => format!("{}/{path}", self.url),
+        };
+        let (parts, body) = request.into_parts();
+        let mut headers = parts.headers;
+        headers.remove(HOST);
+        // The response body is forwarded as is, so asks the dev server not to compress it.
+        headers.remove(ACCEPT_ENCODING);
+        let response = self
+            .client
+            .request(parts.method, url)
+            .headers(headers)
+            .body(body)
+            .send()?;
+
+        let mut builder = Response::builder().status(response.status());
+        for (name, value) in response.headers() {
+            if name != TRANSFER_ENCODING && name != CONTENT_LENGTH {
+                builder = builder.header(name, value);
+            }
+        }
+        Ok(build(builder, response.bytes()?.to_vec()))
+    }
+}
@@ -0,0 +1,409 @@
+use crate::prelude::{AccessDeniedReason, ProtocolAccess, ProtocolAccessDenied, ProtocolRequest, ProtocolResponse};
+use crate::webview::handlers::WryEvents;
+use crate::webview::load_webview::protocol::mime::mime_type;
+use crate::webview::protocol_access::{escapes_root, normalize_path};
+use bevy::asset::io::{AssetReaderError, AssetSourceId};
+use bevy::asset::AssetServerMode;
+use bevy::prelude::{AssetServer, Entity};
+use std::fs::File;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use wry::http::header::{ACCEPT_RANGES, ALLOW, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_NONE_MATCH, RANGE};
+use wry::http::response::Builder;
+use wry::http::{Method, Response, StatusCode};
+
+/// Reads local resources from the asset source specified in [`WebviewWryPlugin`](crate::prelude::WebviewWryPlugin).
+#[derive(Clone)]
+pub(crate) struct LocalResourceReader {
+    pub(crate) webview_entity: Entity,
+    pub(crate) asset_server: Option<AssetServer>,
+    pub(crate) source: AssetSourceId<'static>,
+    pub(crate) root: PathBuf,
+    pub(crate) disk_root: Option<PathBuf>,
+    pub(crate) access: ProtocolAccess,
+    pub(crate) access_denied: WryEvents<ProtocolAccessDenied>,
+}
+
+impl LocalResourceReader {
+    /// Resolves the request path into the path relative to the local root,
+    /// or notifies [`ProtocolAccessDenied`] if access to it is not permitted.
+    fn resolve(&self, request_path: &str, path: &str) -> Option<String> {
+        let resolved = normalize_path(path).and_then(|path| {
+            self.access.check(&path)?;
+            if self.disk_root.as_ref().is_some_and(|root| escapes_root(root, &path)) {
+                return Err(AccessDeniedReason::OutsideRoot);
+            }
+            Ok(path)
+        });
+        match resolved {
+            Ok(path) => Some(path),
+            Err(reason) => {
+                self.access_denied.push(ProtocolAccessDenied {
+                    webview_entity: self.webview_entity,
+                    path: request_path.to_string(),
+                    reason,
+                });
+                None
+            }
+        }
+    }
+
+    /// Opens the gzip-compressed variant `<path>.gz` in place of `path`, which does not exist.
+    ///
+    /// Webviews don't decode `Content-Encoding` of custom protocols, so the variant is decompressed here.
+    async fn open_gzip(&self, request_path: &str, path: &str) -> Result<LocalContent, AssetReaderError> {
+        let variant = self
+            .resolve(request_path, &format!("{path}.gz"))
+            .ok_or_else(|| AssetReaderError::NotFound(self.root.join(path)))?;
+        let compressed = self.open(&variant).await?;
+        let len = compressed.len();
+        let compressed = compressed.read_range(0..len)?;
+        let mut content = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut content)?;
+        Ok(LocalContent::Memory(content))
+    }
+
+    /// Opens the resource.
+    ///
+    /// Files read directly from the disk are not loaded into memory until the requested range is read.
+    async fn open(&self, path: &str) -> Result<LocalContent, AssetReaderError> {
+        match self.disk_path(path) {
+            Some(disk_path) => LocalContent::open_file(&disk_path),
+            None => self.read(path).await.map(LocalContent::Memory),
+        }
+    }
+
+    /// Returns the path on the disk if the asset source reads the files under the local root as they are.
+    fn disk_path(&self, path: &str) -> Option<PathBuf> {
+        let unprocessed = self
+            .asset_server
+            .as_ref()
+            .is_none_or(|asset_server| matches!(asset_server.mode(), AssetServerMode::Unprocessed));
+        unprocessed
+            .then(|| self.disk_root.as_ref().map(|root| root.join(path)))
+            .flatten()
+    }
+
+    async fn read(&self, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        let path = self.root.join(path);
+        let Some(asset_server) = self.asset_server.as_ref() else {
+            // `AssetPlugin` has not been added, so falls back to the default `assets` directory.
+            return std::fs::read(PathBuf::from("assets").join(&path)).map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => AssetReaderError::NotFound(path),
+                _ => e.into(),
+            });
+        };
+        let source = asset_server
+            .get_source(self.source.clone())
+            .map_err(std::io::Error::other)?;
+        let reader = match asset_server.mode() {
+            AssetServerMode::Processed => source
+                .processed_reader()
+                .map_err(std::io::Error::other)?,
+            AssetServerMode::Unprocessed => source.reader(),
+        };
+        let mut content = Vec::new();
+        reader
+            .read(&path)
+            .await?
+            .read_to_end(&mut content)
+            .await?;
+        Ok(content)
+    }
+}
+
+/// Creates the response of the local resource requested via the `flurx` protocol.
+///
+/// `path` is the request path relative to the root of `reader`.
+///
+/// Supports `HEAD`, `Range` and `If-None-Match` in addition to `GET`.
+pub(crate) async fn get_response(
+    request: &ProtocolRequest,
+    path: &str,
+    reader: &LocalResourceReader,
+) -> ProtocolResponse {
+    let method = request.method();
+    if method != Method::GET && method != Method::HEAD {
+        let mut response = status_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+        response.headers_mut().insert(ALLOW, "GET, HEAD".parse().unwrap());
+        return response;
+    }
+    let Some(path) = reader.resolve(request.uri().path(), path) else {
+        return status_response(StatusCode::FORBIDDEN, "forbidden");
+    };
+    let content = match reader.open(&path).await {
+        Err(AssetReaderError::NotFound(_)) => reader.open_gzip(request.uri().path(), &path).await,
+        content => content,
+    };
+    let content = match content {
+        Ok(content) => content,
+        Err(AssetReaderError::NotFound(_)) => return status_response(StatusCode::NOT_FOUND, "not found"),
+        Err(AssetReaderError::Io(e)) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            return status_response(StatusCode::FORBIDDEN, "forbidden");
+        }
+        Err(e) => return status_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    };
+
+    let etag = content.entity_tag();
+    let builder = Response::builder()
+        .header(CONTENT_TYPE, mime_type(&path))
+        .header(ETAG, &etag)
+        .header(ACCEPT_RANGES, "bytes");
+    if header_str(request, IF_NONE_MATCH).is_some_and(|if_none_match| matches_entity_tag(if_none_match, &etag)) {
+        return build(builder.status(StatusCode::NOT_MODIFIED), Vec::new());
+    }
+
+    let len = content.len();
+    let (builder, range) = match header_str(request, RANGE).and_then(|range| parse_range(range, len)) {
+        Some(ByteRange::Satisfiable(range)) => (
+            builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(CONTENT_RANGE, format!("bytes {}-{}/{len}", range.start, range.end - 1)),
+            range,
+        ),
+        Some(ByteRange::Unsatisfiable) => {
+            let builder = builder
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(CONTENT_RANGE, format!("bytes */{len}"));
+            return build(builder, Vec::new());
+        }
+        None => (builder.status(StatusCode::OK), 0..len),
+    };
+    let builder = builder.header(CONTENT_LENGTH, range.len());
+    if method == Method::HEAD {
+        return build(builder, Vec::new());
+    }
+    match content.read_range(range) {
+        Ok(body) => build(builder, body),
+        Err(e) => status_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+/// The content of the local resource.
+enum LocalContent {
+    /// The file on the disk, which is read only in the requested range.
+    File {
+        file: File,
+        len: usize,
+        modified_nanos: u128,
+    },
+
+    /// The content read from the asset source.
+    Memory(Vec<u8>),
+}
+
+impl LocalContent {
+    fn open_file(path: &Path) -> Result<Self, AssetReaderError> {
+        let not_found = || AssetReaderError::NotFound(path.to_path_buf());
+        let file = File::open(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => not_found(),
+            _ => e.into(),
+        })?;
+        let metadata = file.metadata()?;
+        if metadata.is_dir() {
+            return Err(not_found());
+        }
+        let modified_nanos = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |modified| modified.as_nanos());
+        Ok(Self::File {
+            file,
+            len: metadata.len() as usize,
+            modified_nanos,
+        })
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::File { len, .. } => *len,
+            Self::Memory(content) => content.len(),
+        }
+    }
+
+    /// Returns the entity tag, which is derived from the metadata for files on the disk.
+    fn entity_tag(&self) -> String {
+        match self {
+            Self::File { len, modified_nanos, .. } => format!("\"{len:x}-{modified_nanos:x}\""),
+            Self::Memory(content) => {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                format!("\"{:x}-{:x}\"", content.len(), hasher.finish())
+            }
+        }
+    }
+
+    fn read_range(self, range: Range<usize>) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::File { mut file, .. } => {
+                let mut body = Vec::with_capacity(range.len());
+                file.seek(SeekFrom::Start(range.start as u64))?;
+                file.take(range.len() as u64).read_to_end(&mut body)?;
+                Ok(body)
+            }
+            Self::Memory(content) if range.len() == content.len() => Ok(content),
+            Self::Memory(content) => Ok(content[range].to_vec()),
+        }
+    }
+}
+
+pub(crate) fn status_response(status: StatusCode, message: &str) -> ProtocolResponse {
+    let mut response = Response::new(message.as_bytes().to_vec());
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+    response
+}
+
+pub(crate) fn build(builder: Builder, body: Vec<u8>) -> ProtocolResponse {
+    builder
+        .body(body)
+        .unwrap_or_else(|e| status_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()))
+}
+
+fn header_str(request: &ProtocolRequest, name: wry::http::header::HeaderName) -> Option<&str> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn matches_entity_tag(if_none_match: &str, etag: &str) -> bool {
+    let if_none_match = if_none_match.trim();
+    if_none_match == "*" || if_none_match
+        .split(',')
+        .any(|tag| tag.trim().trim_start_matches("W/") == etag)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum ByteRange {
+    Satisfiable(Range<usize>),
+    Unsatisfiable,
+}
+
+/// Parses the single range of the `Range` header.
+///
+/// Returns `None` if the header is malformed or contains multiple ranges,
+/// in which case the header is ignored and the whole content is returned.
+fn parse_range(range: &str, len: usize) -> Option<ByteRange> {
+    let range = range.trim().strip_prefix("bytes=")?;
+    if range.contains(',') {
+        return None;
+    }
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        let suffix = end.parse::<usize>().ok()?;
+        if suffix == 0 || len == 0 {
+            return Some(ByteRange::Unsatisfiable);
+        }
+        return Some(ByteRange::Satisfiable(len.saturating_sub(suffix)..len));
+    }
+    let start = start.parse::<usize>().ok()?;
+    let end = if end.is_empty() {
+        None
+    } else {
+        Some(end.parse::<usize>().ok()?)
+    };
+    if end.is_some_and(|end| end < start) {
+        return None;
+    }
+    if len <= start {
+        return Some(ByteRange::Unsatisfiable);
+    }
+    let end = end.map_or(len, |end| end.min(len - 1) + 1);
+    Some(ByteRange::Satisfiable(start..end))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::ProtocolAccess;
+    use crate::webview::handlers::WryEvents;
+    use crate::webview::load_webview::protocol::local_resource::{get_response, matches_entity_tag, parse_range, ByteRange, LocalContent, LocalResourceReader};
+    use bevy::asset::io::AssetSourceId;
+    use bevy::prelude::Entity;
+    use bevy::tasks::block_on;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use wry::http::header::CONTENT_ENCODING;
+    use wry::http::{Request, StatusCode};
+
+    #[test]
+    fn parse_bounded_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some(ByteRange::Satisfiable(0..100)));
+        assert_eq!(parse_range("bytes=900-2000", 1000), Some(ByteRange::Satisfiable(900..1000)));
+    }
+
+    #[test]
+    fn parse_open_and_suffix_range() {
+        assert_eq!(parse_range("bytes=500-", 1000), Some(ByteRange::Satisfiable(500..1000)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some(ByteRange::Satisfiable(900..1000)));
+        assert_eq!(parse_range("bytes=-2000", 1000), Some(ByteRange::Satisfiable(0..1000)));
+    }
+
+    #[test]
+    fn unsatisfiable_range() {
+        assert_eq!(parse_range("bytes=1000-", 1000), Some(ByteRange::Unsatisfiable));
+        assert_eq!(parse_range("bytes=-0", 1000), Some(ByteRange::Unsatisfiable));
+    }
+
+    #[test]
+    fn ignore_malformed_range() {
+        assert_eq!(parse_range("items=0-1", 1000), None);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), None);
+        assert_eq!(parse_range("bytes=10-5", 1000), None);
+        assert_eq!(parse_range("bytes=a-b", 1000), None);
+    }
+
+    #[test]
+    fn match_entity_tag() {
+        assert!(matches_entity_tag("\"a\"", "\"a\""));
+        assert!(matches_entity_tag("\"b\", W/\"a\"", "\"a\""));
+        assert!(matches_entity_tag("*", "\"a\""));
+        assert!(!matches_entity_tag("\"b\"", "\"a\""));
+    }
+
+    #[test]
+    fn serve_decompressed_gzip_variant() {
+        let dir = std::env::temp_dir().join(format!("flurx_gzip_variant_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"console.log(1)").unwrap();
+        std::fs::write(dir.join("main.js.gz"), encoder.finish().unwrap()).unwrap();
+
+        let reader = LocalResourceReader {
+            webview_entity: Entity::PLACEHOLDER,
+            asset_server: None,
+            source: AssetSourceId::Default,
+            root: PathBuf::new(),
+            disk_root: Some(dir.clone()),
+            access: ProtocolAccess::default(),
+            access_denied: WryEvents::default(),
+        };
+        let request = Request::get("flurx://localhost/main.js").body(Vec::new()).unwrap();
+        let response = block_on(get_response(&request, "main.js", &reader));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.body(), b"console.log(1)");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn read_range_of_file() {
+        let path = std::env::temp_dir().join(format!("flurx_read_range_{}.txt", std::process::id()));
+        std::fs::write(&path, b"0123456789").unwrap();
+        let content = LocalContent::open_file(&path).unwrap();
+        assert_eq!(content.len(), 10);
+        assert!(content.entity_tag().starts_with("\"a-"));
+        assert_eq!(content.read_range(2..5).unwrap(), b"234");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn directory_is_not_found() {
+        assert!(LocalContent::open_file(&std::env::temp_dir()).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+/// Returns the mime type guessed from the extension of `path`.
+///
+/// Unknown extensions fall back to `application/octet-stream`.
+pub(crate) fn mime_type(path: &str) -> &'static str {
+    let Some(extension) = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    else {
+        return OCTET_STREAM;
+    };
+    match extension.to_ascii_lowercase().as_str() {
+        "htm" | "html" => "text/html",
+        "txt" => "text/plain",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "ics" => "text/calendar",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "json" | "map" => "application/json",
+        "xml" => "application/xml",
+        "xhtml" => "application/xhtml+xml",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        "rtf" => "application/rtf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "lzh" => "application/x-lzh",
+        "bz" => "application/x-bzip",
+        "bz2" => "application/x-bzip2",
+        "7z" => "application/x-7z-compressed",
+        "rar" => "application/vnd.rar",
+        "arc" => "application/x-freearc",
+        "jar" => "application/java-archive",
+        "epub" => "application/epub+zip",
+        "abw" => "application/x-abiword",
+        "azw" => "application/vnd.amazon.ebook",
+        "csh" => "application/x-csh",
+        "sh" => "application/x-sh",
+        "swf" => "application/x-shockwave-flash",
+        "mpkg" => "application/vnd.apple.installer+xml",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "odp" => "application/vnd.oasis.opendocument.presentation",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "vsd" => "application/vnd.visio",
+        "ogx" => "application/ogg",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/vnd.microsoft.icon",
+        "tif" | "tiff" => "image/tiff",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "eot" => "application/vnd.ms-fontobject",
+        "mp3" => "audio/mpeg",
+        "aac" => "audio/aac",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "weba" => "audio/webm",
+        "oga" | "ogg" | "opus" => "audio/ogg",
+        "mid" | "midi" => "audio/midi",
+        "mp4" => "video/mp4",
+        "mpeg" => "video/mpeg",
+        "webm" => "video/webm",
+        "ogv" => "video/ogg",
+        "avi" => "video/x-msvideo",
+        "mov" => "video/quicktime",
+        _ => OCTET_STREAM,
+    }
+}
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::load_webview::protocol::mime::mime_type;
+
+    #[test]
+    fn known_extensions() {
+        assert_eq!(mime_type("index.html"), "text/html");
+        assert_eq!(mime_type("module.mjs"), "text/javascript");
+        assert_eq!(mime_type("fonts/icon.woff2"), "font/woff2");
+        assert_eq!(mime_type("images/bg.webp"), "image/webp");
+        assert_eq!(mime_type("main.js.map"), "application/json");
+        assert_eq!(mime_type("bgm.mp3"), "audio/mpeg");
+        assert_eq!(mime_type("movie.webm"), "video/webm");
+    }
+
+    #[test]
+    fn extension_is_case_insensitive() {
+        assert_eq!(mime_type("IMAGE.PNG"), "image/png");
+    }
+
+    #[test]
+    fn fallback_to_octet_stream() {
+        assert_eq!(mime_type("data.unknown"), "application/octet-stream");
+        assert_eq!(mime_type("LICENSE"), "application/octet-stream");
+    }
+}
@@ -0,0 +1,180 @@
+//! Specifies where local resources served from the `flurx` protocol come from.
+
+use bevy::prelude::{App, Component, Reflect, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectSerialize, Resource};
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Represents the source of local resources served from `flurx://localhost/`.
+///
+/// This is specified globally by [`WebviewWryPlugin::local_root`](crate::prelude::WebviewWryPlugin::local_root),
+/// and can be overridden per webview by inserting this as a component.
+///
+/// ## Examples
+///
+/// Proxies to the dev server only in debug builds,
+/// so `WebviewUri`, CSP and IPC setup stay the same as in release builds.
+/// This requires the `dev_server` feature.
+///
+/// ```no_run
+/// # #[cfg(feature = "dev_server")]
+/// # {
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// let local_root = if cfg!(debug_assertions) {
+///     LocalRoot::DevServer("http://localhost:5173".to_string())
+/// } else {
+///     LocalRoot::Assets("ui".into())
+/// };
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin {
+///             local_root,
+///             ..default()
+///         },
+///     ));
+/// # }
+/// ```
+///
+/// Overrides the local root of the webview.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// fn spawn_webview(mut commands: Commands) {
+///     commands.spawn((
+///         Webview::Uri(WebviewUri::relative_local("index.html")),
+///         LocalRoot::Assets("editor_ui".into()),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Eq, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+pub enum LocalRoot {
+    /// The directory located under the root of [`WebviewWryPlugin::asset_source`](crate::prelude::WebviewWryPlugin::asset_source).
+    Assets(PathBuf),
+
+    /// The url of the development server such as `http://localhost:5173`.
+    ///
+    /// All requests to `flurx://localhost/*` are proxied to the server,
+    /// and [`ProtocolAccess`](crate::prelude::ProtocolAccess) is not applied.
+    ///
+    /// To make HMR work, the websocket of the dev server must be reachable from the webview;
+    /// in Vite, set `server.hmr.clientPort` to the port of the dev server.
+    ///
+    /// This variant is available only with the `dev_server` feature.
+    #[cfg(feature = "dev_server")]
+    DevServer(String),
+}
+
+impl LocalRoot {
+    /// Returns [`LocalRoot::DevServer`] if the environment variable `key` is set,
+    /// otherwise [`LocalRoot::Assets`] with `dir`.
+    ///
+    /// This requires the `dev_server` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// // FLURX_DEV_SERVER=http://localhost:5173 cargo run
+    /// LocalRoot::dev_server_or_assets("FLURX_DEV_SERVER", "ui");
+    /// ```
+    #[cfg(feature = "dev_server")]
+    pub fn dev_server_or_assets(key: &str, dir: impl Into<PathBuf>) -> Self {
+        match std::env::var(key) {
+            Ok(url) if !url.is_empty() => Self::DevServer(url),
+            _ => Self::Assets(dir.into()),
+        }
+    }
+}
+
+impl Default for LocalRoot {
+    fn default() -> Self {
+        Self::Assets(PathBuf::from("ui"))
+    }
+}
+
+impl From<PathBuf> for LocalRoot {
+    #[inline]
+    fn from(dir: PathBuf) -> Self {
+        Self::Assets(dir)
+    }
+}
+
+/// Registers the named mounts of the `flurx` protocol.
+pub trait WebviewMountExt {
+    /// Serves `flurx://localhost/<name>/*` from `root` in all webviews.
+    ///
+    /// Mounts take precedence over [`LocalRoot`] of the webview.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// // flurx://localhost/hud/index.html serves assets/hud/index.html,
+    /// // and flurx://localhost/editor/index.html serves assets/editor_ui/index.html.
+    /// App::new()
+    ///     .add_plugins((DefaultPlugins, WebviewWryPlugin::default()))
+    ///     .add_webview_mount("hud", LocalRoot::Assets("hud".into()))
+    ///     .add_webview_mount("editor", LocalRoot::Assets("editor_ui".into()));
+    /// ```
+    fn add_webview_mount(&mut self, name: impl Into<String>, root: LocalRoot) -> &mut Self;
+}
+
+impl WebviewMountExt for App {
+    fn add_webview_mount(&mut self, name: impl Into<String>, root: LocalRoot) -> &mut Self {
+        self
+            .world_mut()
+            .get_resource_or_insert_with::<WebviewMounts>(WebviewMounts::default)
+            .0
+            .insert(name.into(), root);
+        self
+    }
+}
+
+#[derive(Resource, Default, Clone)]
+pub(crate) struct WebviewMounts(pub HashMap<String, LocalRoot>);
+
+/// Resolves the request `path` into the mount prefix such as `hud/`,
+/// the root which serves it, and the path relative to the root.
+pub(crate) fn resolve_mount<'a, T>(
+    mounts: &'a HashMap<String, T>,
+    default: &'a T,
+    path: &'a str,
+) -> (&'a str, &'a T, &'a str) {
+    let path = path.trim_start_matches('/');
+    let (name, rest) = path.split_once('/').unwrap_or((path, ""));
+    match mounts.get(name) {
+        Some(root) => (&path[..path.len() - rest.len()], root, rest),
+        None => ("", default, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::local_root::resolve_mount;
+    use bevy::utils::HashMap;
+
+    #[test]
+    fn resolve_mounted_path() {
+        let mounts = HashMap::from_iter([("hud".to_string(), 1)]);
+        assert_eq!(resolve_mount(&mounts, &0, "/hud/js/main.js"), ("hud/", &1, "js/main.js"));
+        assert_eq!(resolve_mount(&mounts, &0, "/hud/"), ("hud/", &1, ""));
+        assert_eq!(resolve_mount(&mounts, &0, "/hud"), ("hud", &1, ""));
+    }
+
+    #[test]
+    fn fallback_to_default_root() {
+        let mounts = HashMap::from_iter([("hud".to_string(), 1)]);
+        assert_eq!(resolve_mount(&mounts, &0, "/index.html"), ("", &0, "index.html"));
+        assert_eq!(resolve_mount(&mounts, &0, "/hudson/index.html"), ("", &0, "hudson/index.html"));
+        assert_eq!(resolve_mount(&mounts, &0, "/"), ("", &0, ""));
+    }
+}
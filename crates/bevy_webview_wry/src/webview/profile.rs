@@ -0,0 +1,93 @@
+//! Shares [`WebContext`] between webviews with the same [`WebviewProfile`].
+
+use bevy::log::warn;
+use bevy::utils::HashMap;
+use bevy_webview_core::prelude::WebviewProfile;
+use std::path::{Path, PathBuf};
+use wry::WebContext;
+
+/// The [`WebContext`] of each profile.
+///
+/// The contexts are kept until the app exits, so that webviews created later in the same profile share them.
+pub(crate) struct WryWebContexts {
+    root: Option<PathBuf>,
+    contexts: HashMap<String, WebContext>,
+}
+
+impl WryWebContexts {
+    /// Creates the contexts whose data directories are located under `root`.
+    ///
+    /// If `root` is `None`, `<data_dir>/<executable name>/webview_profiles` is used.
+    pub(crate) fn new(root: Option<PathBuf>) -> Self {
+        let root = root.or_else(default_profiles_dir);
+        if root.is_none() {
+            warn!("The data directory for webview profiles was not found; profiles use the default data directory.");
+        }
+        Self {
+            root,
+            contexts: HashMap::default(),
+        }
+    }
+
+    /// Returns the context of `profile`, creating it if it does not exist yet.
+    pub(crate) fn get_or_create(&mut self, profile: &WebviewProfile) -> &mut WebContext {
+        let root = self.root.as_deref();
+        self.contexts
+            .entry(profile.0.clone())
+            .or_insert_with(|| WebContext::new(root.map(|root| profile_dir(root, &profile.0))))
+    }
+}
+
+fn default_profiles_dir() -> Option<PathBuf> {
+    let app_name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "bevy_webview".to_string());
+    Some(dirs::data_dir()?.join(app_name).join("webview_profiles"))
+}
+
+/// Returns the data directory of the profile `name`.
+///
+/// Characters other than ASCII alphanumerics, `-` and `_` are percent-encoded,
+/// so that the name can't escape `root` and different names never share a directory.
+fn profile_dir(root: &Path, name: &str) -> PathBuf {
+    let dir_name = name
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect::<String>();
+    root.join(dir_name)
+}
+
+/// Returns the identifier of the data store of the profile `name` on macOS and iOS,
+/// where the data directory of [`WebContext`] is not supported.
+///
+/// The identifier is derived from the name with FNV-1a, so that it stays the same between sessions.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub(crate) fn data_store_identifier(name: &str) -> [u8; 16] {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let fnv = |offset: u64| name.bytes().fold(offset, |hash, b| (hash ^ b as u64).wrapping_mul(PRIME));
+    let mut identifier = [0; 16];
+    identifier[..8].copy_from_slice(&fnv(0xcbf2_9ce4_8422_2325).to_le_bytes());
+    identifier[8..].copy_from_slice(&fnv(0x6c62_272e_07bb_0142).to_le_bytes());
+    identifier
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::profile::profile_dir;
+    use std::path::Path;
+
+    #[test]
+    fn keep_safe_names() {
+        assert_eq!(profile_dir(Path::new("profiles"), "player_1-main"), Path::new("profiles").join("player_1-main"));
+    }
+
+    #[test]
+    fn encode_unsafe_characters() {
+        assert_eq!(profile_dir(Path::new("profiles"), "../a b"), Path::new("profiles").join("%2E%2E%2Fa%20b"));
+        assert_ne!(profile_dir(Path::new("profiles"), "a b"), profile_dir(Path::new("profiles"), "a_b"));
+    }
+}
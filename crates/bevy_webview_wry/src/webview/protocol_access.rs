@@ -0,0 +1,220 @@
+//! Restricts the local resources served from the `flurx` protocol.
+
+use crate::util::glob_match;
+use crate::webview::handlers::RegisterWryEvent;
+use bevy::prelude::{App, Entity, Event, Plugin, Reflect, ReflectDefault, ReflectResource, Resource};
+use std::path::Path;
+
+/// Controls which local resources can be served from the `flurx` protocol.
+///
+/// Regardless of this setting, paths which resolve outside the local root are always denied.
+///
+/// Patterns are globs matched against the path relative to the local root such as `js/main.js`.
+/// `*` does not match `/`, while `**` matches any number of directories.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin {
+///             protocol_access: ProtocolAccess {
+///                 deny: vec!["**/*.map".to_string(), "private/**".to_string()],
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///     ));
+/// ```
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ProtocolAccess {
+    /// The patterns of paths that are allowed to be served.
+    ///
+    /// If empty, all paths are allowed.
+    pub allow: Vec<String>,
+
+    /// The patterns of paths that are denied.
+    ///
+    /// This takes precedence over [`ProtocolAccess::allow`].
+    pub deny: Vec<String>,
+
+    /// Whether to serve the files or directories whose name starts with `.`.
+    ///
+    /// Default is `false`.
+    pub allow_dotfiles: bool,
+}
+
+impl ProtocolAccess {
+    pub(crate) fn check(&self, path: &str) -> Result<(), AccessDeniedReason> {
+        if !self.allow_dotfiles && path.split('/').any(|segment| segment.starts_with('.')) {
+            return Err(AccessDeniedReason::Dotfile);
+        }
+        if self.deny.iter().any(|pattern| glob_match(pattern, path)) {
+            return Err(AccessDeniedReason::Pattern);
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|pattern| glob_match(pattern, path)) {
+            return Err(AccessDeniedReason::Pattern);
+        }
+        Ok(())
+    }
+}
+
+/// Fired when the request to the `flurx` protocol was denied.
+///
+/// The webview receives `403 Forbidden`.
+#[derive(Event, Clone, Debug, Eq, PartialEq, Reflect)]
+pub struct ProtocolAccessDenied {
+    /// The entity associated with the webview that sent the request.
+    pub webview_entity: Entity,
+
+    /// The requested path.
+    pub path: String,
+
+    /// The reason why the request was denied.
+    pub reason: AccessDeniedReason,
+}
+
+/// Represents the reason why the request to the `flurx` protocol was denied.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Reflect)]
+pub enum AccessDeniedReason {
+    /// The path resolves outside the local root via `..` or a symbolic link.
+    OutsideRoot,
+
+    /// The path contains a dotfile and [`ProtocolAccess::allow_dotfiles`] is `false`.
+    Dotfile,
+
+    /// The path is denied by [`ProtocolAccess::deny`] or not matched by [`ProtocolAccess::allow`].
+    Pattern,
+}
+
+/// Decodes the request path and normalizes it into the path relative to the local root.
+pub(crate) fn normalize_path(path: &str) -> Result<String, AccessDeniedReason> {
+    let path = percent_decode(path);
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => continue,
+            ".." => return Err(AccessDeniedReason::OutsideRoot),
+            _ if segment.contains(['\\', ':', '\0']) => return Err(AccessDeniedReason::OutsideRoot),
+            _ => segments.push(segment),
+        }
+    }
+    if segments.is_empty() {
+        Ok("index.html".to_string())
+    } else {
+        Ok(segments.join("/"))
+    }
+}
+
+/// Returns whether `path` resolves outside `root` on the disk by following symbolic links.
+///
+/// Paths that do not exist are not considered escaping, while paths that can't be resolved for other reasons are.
+pub(crate) fn escapes_root(root: &Path, path: &str) -> bool {
+    let resolved = root
+        .canonicalize()
+        .and_then(|root| Ok((root.join(path).canonicalize()?, root)));
+    match resolved {
+        Ok((resolved, root)) => !resolved.starts_with(root),
+        Err(e) => e.kind() != std::io::ErrorKind::NotFound,
+    }
+}
+
+/// Decodes the percent-encoded bytes of `path`.
+pub(crate) fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+pub(crate) struct ProtocolAccessPlugin;
+
+impl Plugin for ProtocolAccessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ProtocolAccess>()
+            .register_type::<AccessDeniedReason>()
+            .init_resource::<ProtocolAccess>()
+            .register_wry_event::<ProtocolAccessDenied>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::protocol_access::{escapes_root, normalize_path, AccessDeniedReason, ProtocolAccess};
+
+    #[test]
+    fn normalize_relative_path() {
+        assert_eq!(normalize_path("/"), Ok("index.html".to_string()));
+        assert_eq!(normalize_path("/js/./main.js"), Ok("js/main.js".to_string()));
+        assert_eq!(normalize_path("/my%20file.txt"), Ok("my file.txt".to_string()));
+    }
+
+    #[test]
+    fn deny_path_traversal() {
+        assert_eq!(normalize_path("/../../secret"), Err(AccessDeniedReason::OutsideRoot));
+        assert_eq!(normalize_path("/js/%2e%2e/%2e%2e/secret"), Err(AccessDeniedReason::OutsideRoot));
+        assert_eq!(normalize_path("/..%2Fsecret"), Err(AccessDeniedReason::OutsideRoot));
+        assert_eq!(normalize_path("/..%5Csecret"), Err(AccessDeniedReason::OutsideRoot));
+        assert_eq!(normalize_path("/C:%5Csecret"), Err(AccessDeniedReason::OutsideRoot));
+    }
+
+    #[test]
+    fn deny_dotfiles_by_default() {
+        let access = ProtocolAccess::default();
+        assert_eq!(access.check(".env"), Err(AccessDeniedReason::Dotfile));
+        assert_eq!(access.check(".git/config"), Err(AccessDeniedReason::Dotfile));
+        assert_eq!(access.check("index.html"), Ok(()));
+
+        let access = ProtocolAccess {
+            allow_dotfiles: true,
+            ..Default::default()
+        };
+        assert_eq!(access.check(".well-known/a.json"), Ok(()));
+    }
+
+    #[test]
+    fn deny_takes_precedence_over_allow() {
+        let access = ProtocolAccess {
+            allow: vec!["**/*.js".to_string(), "index.html".to_string()],
+            deny: vec!["vendor/**".to_string()],
+            allow_dotfiles: false,
+        };
+        assert_eq!(access.check("index.html"), Ok(()));
+        assert_eq!(access.check("js/main.js"), Ok(()));
+        assert_eq!(access.check("vendor/lib.js"), Err(AccessDeniedReason::Pattern));
+        assert_eq!(access.check("style.css"), Err(AccessDeniedReason::Pattern));
+    }
+
+    #[test]
+    fn deny_unresolvable_path() {
+        let root = std::env::temp_dir().join(format!("flurx_escapes_root_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("file.txt"), b"").unwrap();
+        assert!(!escapes_root(&root, "file.txt"));
+        assert!(!escapes_root(&root, "missing.txt"));
+        assert!(escapes_root(&root, "file.txt/child"));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+//! Adds response headers to the `flurx` protocol.
+
+use crate::prelude::{ProtocolRequest, ProtocolResponse};
+use crate::util::glob_match;
+use crate::webview::protocol_access::normalize_path;
+use bevy::prelude::{App, Plugin, Reflect, ReflectDefault, ReflectResource, Resource};
+use wry::http::header::{ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY};
+use wry::http::{HeaderMap, HeaderName, HeaderValue, Method};
+
+/// Headers added to the responses of the `flurx` protocol,
+/// including local resources, [routes](crate::prelude::WebviewRouteExt) and the dev server.
+///
+/// These headers override the headers set by the protocol itself.
+/// Headers with an invalid name or value are ignored.
+///
+/// ## Examples
+///
+/// Enables `SharedArrayBuffer` and allows `https://example.com` to call routes.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         WebviewWryPlugin {
+///             protocol_headers: ProtocolHeaders::default()
+///                 .cross_origin_isolated()
+///                 .with_path_header("wasm/**", "Cache-Control", "no-cache")
+///                 .with_cors(ProtocolCors {
+///                     allow_origins: vec!["https://example.com".to_string()],
+///                     ..default()
+///                 }),
+///             ..default()
+///         },
+///     ));
+/// ```
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ProtocolHeaders {
+    /// The headers added to all responses.
+    pub global: Vec<ProtocolHeader>,
+
+    /// The headers added to the responses whose paths match.
+    ///
+    /// If the same header is specified in multiple places, the last one is used.
+    pub paths: Vec<PathHeaders>,
+
+    /// The CORS settings.
+    ///
+    /// If `None`, CORS headers are not added.
+    pub cors: Option<ProtocolCors>,
+}
+
+/// Represents a single response header.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct ProtocolHeader {
+    /// The header name such as `Cross-Origin-Opener-Policy`.
+    pub name: String,
+
+    /// The header value such as `same-origin`.
+    pub value: String,
+}
+
+/// The headers added to the responses whose paths match [`PathHeaders::path`].
+#[derive(Debug, Clone, Default, Eq, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct PathHeaders {
+    /// The glob pattern of the path relative to `flurx://localhost/`.
+    ///
+    /// `*` does not match `/`, while `**` matches any number of directories.
+    pub path: String,
+
+    /// The headers to add.
+    pub headers: Vec<ProtocolHeader>,
+}
+
+/// Represents the [CORS](https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS) settings of the `flurx` protocol.
+///
+/// Preflight requests are answered with `204 No Content`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct ProtocolCors {
+    /// The glob patterns of the allowed origins such as `https://*.example.com`.
+    ///
+    /// `*` allows all origins.
+    pub allow_origins: Vec<String>,
+
+    /// The allowed methods.
+    ///
+    /// If empty, the requested method is allowed.
+    pub allow_methods: Vec<String>,
+
+    /// The allowed request headers.
+    ///
+    /// If empty, the requested headers are allowed.
+    pub allow_headers: Vec<String>,
+
+    /// The response headers exposed to the page.
+    pub expose_headers: Vec<String>,
+
+    /// Whether to allow credentials such as cookies.
+    pub allow_credentials: bool,
+
+    /// How long, in seconds, the result of the preflight request can be cached.
+    pub max_age: Option<u64>,
+}
+
+impl ProtocolHeaders {
+    /// Adds the header to all responses.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.global.push(ProtocolHeader {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Adds the header to the responses whose paths match the glob pattern `path`.
+    pub fn with_path_header(mut self, path: impl Into<String>, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let path = path.into();
+        let header = ProtocolHeader {
+            name: name.into(),
+            value: value.into(),
+        };
+        match self.paths.iter_mut().find(|headers| headers.path == path) {
+            Some(headers) => headers.headers.push(header),
+            None => self.paths.push(PathHeaders {
+                path,
+                headers: vec![header],
+            }),
+        }
+        self
+    }
+
+    /// Adds `Cross-Origin-Opener-Policy: same-origin` and `Cross-Origin-Embedder-Policy: require-corp`
+    /// to all responses, which are required to use `SharedArrayBuffer`.
+    pub fn cross_origin_isolated(self) -> Self {
+        self
+            .with_header("Cross-Origin-Opener-Policy", "same-origin")
+            .with_header("Cross-Origin-Embedder-Policy", "require-corp")
+    }
+
+    /// Sets the CORS settings.
+    pub fn with_cors(mut self, cors: ProtocolCors) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Returns whether `request` is the CORS preflight request to be answered by the protocol.
+    pub(crate) fn is_preflight(&self, request: &ProtocolRequest) -> bool {
+        self.cors.is_some()
+            && request.method() == Method::OPTIONS
+            && request.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+    }
+
+    /// Returns the headers to be added to the response to `request`.
+    ///
+    /// Path headers are matched against the normalized path of the served resource, such as `index.html` for `/`,
+    /// and also against the raw path for routes.
+    pub(crate) fn response_headers(&self, request: &ProtocolRequest) -> HeaderMap {
+        let raw_path = request.uri().path().trim_start_matches('/');
+        let normalized_path = normalize_path(request.uri().path()).ok();
+        let mut headers = HeaderMap::new();
+        let path_headers = self
+            .paths
+            .iter()
+            .filter(|headers| {
+                glob_match(&headers.path, raw_path)
+                    || normalized_path.as_ref().is_some_and(|path| glob_match(&headers.path, path))
+            })
+            .flat_map(|headers| headers.headers.iter());
+        for header in self.global.iter().chain(path_headers) {
+            insert(&mut headers, &header.name, &header.value);
+        }
+        if let Some(cors) = self.cors.as_ref() {
+            cors.insert_headers(request, self.is_preflight(request), &mut headers);
+        }
+        headers
+    }
+}
+
+impl ProtocolCors {
+    fn allows(&self, origin: &str) -> bool {
+        self.allow_origins.iter().any(|pattern| pattern == "*" || glob_match(pattern, origin))
+    }
+
+    fn insert_headers(&self, request: &ProtocolRequest, preflight: bool, headers: &mut HeaderMap) {
+        headers.insert(VARY, HeaderValue::from_static("Origin"));
+        let Some(origin) = request.headers().get(ORIGIN).and_then(|origin| origin.to_str().ok()) else {
+            return;
+        };
+        if !self.allows(origin) {
+            return;
+        }
+        if !self.allow_credentials && self.allow_origins.iter().any(|pattern| pattern == "*") {
+            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+        } else {
+            insert(headers, ACCESS_CONTROL_ALLOW_ORIGIN.as_str(), origin);
+        }
+        if self.allow_credentials {
+            headers.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true"));
+        }
+        if !self.expose_headers.is_empty() {
+            insert(headers, ACCESS_CONTROL_EXPOSE_HEADERS.as_str(), &self.expose_headers.join(", "));
+        }
+        if !preflight {
+            return;
+        }
+        let requested = |name| request.headers().get(name).and_then(|value: &HeaderValue| value.to_str().ok());
+        let methods = if self.allow_methods.is_empty() {
+            requested(ACCESS_CONTROL_REQUEST_METHOD).map(str::to_string)
+        } else {
+            Some(self.allow_methods.join(", "))
+        };
+        let allow_headers = if self.allow_headers.is_empty() {
+            requested(ACCESS_CONTROL_REQUEST_HEADERS).map(str::to_string)
+        } else {
+            Some(self.allow_headers.join(", "))
+        };
+        if let Some(methods) = methods {
+            insert(headers, ACCESS_CONTROL_ALLOW_METHODS.as_str(), &methods);
+        }
+        if let Some(allow_headers) = allow_headers {
+            insert(headers, ACCESS_CONTROL_ALLOW_HEADERS.as_str(), &allow_headers);
+        }
+        if let Some(max_age) = self.max_age {
+            insert(headers, ACCESS_CONTROL_MAX_AGE.as_str(), &max_age.to_string());
+        }
+    }
+}
+
+fn insert(headers: &mut HeaderMap, name: &str, value: &str) {
+    if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+        headers.insert(name, value);
+    }
+}
+
+/// Adds `headers` to `response`, overriding the existing headers except `Vary`.
+pub(crate) fn with_headers(mut response: ProtocolResponse, headers: &HeaderMap) -> ProtocolResponse {
+    for (name, value) in headers {
+        let value = match response.headers().get(name) {
+            Some(current) if name == VARY => {
+                let current = current.to_str().unwrap_or_default();
+                match HeaderValue::from_str(&format!("{current}, {}", value.to_str().unwrap_or_default())) {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                }
+            }
+            _ => value.clone(),
+        };
+        response.headers_mut().insert(name, value);
+    }
+    response
+}
+
+pub(crate) struct ProtocolHeadersPlugin;
+
+impl Plugin for ProtocolHeadersPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ProtocolHeaders>()
+            .register_type::<ProtocolHeader>()
+            .register_type::<PathHeaders>()
+            .register_type::<ProtocolCors>()
+            .init_resource::<ProtocolHeaders>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::{ProtocolCors, ProtocolHeaders};
+    use crate::webview::protocol_headers::with_headers;
+    use wry::http::{Method, Request, Response};
+
+    fn request(method: Method, path: &str, headers: &[(&str, &str)]) -> Request<Vec<u8>> {
+        let mut builder = Request::builder().method(method).uri(format!("flurx://localhost{path}"));
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn add_global_and_path_headers() {
+        let headers = ProtocolHeaders::default()
+            .cross_origin_isolated()
+            .with_header("X-Frame-Options", "DENY")
+            .with_path_header("wasm/**", "X-Frame-Options", "SAMEORIGIN");
+        let map = headers.response_headers(&request(Method::GET, "/index.html", &[]));
+        assert_eq!(map["cross-origin-opener-policy"], "same-origin");
+        assert_eq!(map["cross-origin-embedder-policy"], "require-corp");
+        assert_eq!(map["x-frame-options"], "DENY");
+        let map = headers.response_headers(&request(Method::GET, "/wasm/physics.wasm", &[]));
+        assert_eq!(map["x-frame-options"], "SAMEORIGIN");
+    }
+
+    #[test]
+    fn match_path_headers_against_normalized_path() {
+        let headers = ProtocolHeaders::default()
+            .with_path_header("index.html", "X-Frame-Options", "DENY")
+            .with_path_header("wasm/*.wasm", "Cache-Control", "no-cache");
+        let map = headers.response_headers(&request(Method::GET, "/", &[]));
+        assert_eq!(map["x-frame-options"], "DENY");
+        let map = headers.response_headers(&request(Method::GET, "/wasm/my%20file.wasm", &[]));
+        assert_eq!(map["cache-control"], "no-cache");
+        let map = headers.response_headers(&request(Method::GET, "//wasm/./physics.wasm", &[]));
+        assert_eq!(map["cache-control"], "no-cache");
+    }
+
+    #[test]
+    fn allow_cors_origin() {
+        let headers = ProtocolHeaders::default().with_cors(ProtocolCors {
+            allow_origins: vec!["https://*.example.com".to_string()],
+            ..Default::default()
+        });
+        let map = headers.response_headers(&request(Method::GET, "/api/players", &[("Origin", "https://app.example.com")]));
+        assert_eq!(map["access-control-allow-origin"], "https://app.example.com");
+        let map = headers.response_headers(&request(Method::GET, "/api/players", &[("Origin", "https://evil.com")]));
+        assert!(!map.contains_key("access-control-allow-origin"));
+    }
+
+    #[test]
+    fn answer_preflight() {
+        let headers = ProtocolHeaders::default().with_cors(ProtocolCors {
+            allow_origins: vec!["*".to_string()],
+            max_age: Some(600),
+            ..Default::default()
+        });
+        let preflight = request(Method::OPTIONS, "/api/players", &[
+            ("Origin", "https://example.com"),
+            ("Access-Control-Request-Method", "POST"),
+            ("Access-Control-Request-Headers", "content-type"),
+        ]);
+        assert!(headers.is_preflight(&preflight));
+        let map = headers.response_headers(&preflight);
+        assert_eq!(map["access-control-allow-origin"], "*");
+        assert_eq!(map["access-control-allow-methods"], "POST");
+        assert_eq!(map["access-control-allow-headers"], "content-type");
+        assert_eq!(map["access-control-max-age"], "600");
+        assert!(!ProtocolHeaders::default().is_preflight(&preflight));
+    }
+
+    #[test]
+    fn append_vary() {
+        let response = Response::builder().header("Vary", "Accept-Encoding").body(Vec::new()).unwrap();
+        let headers = ProtocolHeaders::default().with_cors(ProtocolCors::default());
+        let response = with_headers(response, &headers.response_headers(&request(Method::GET, "/", &[])));
+        assert_eq!(response.headers()["vary"], "Accept-Encoding, Origin");
+    }
+}
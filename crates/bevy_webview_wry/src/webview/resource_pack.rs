@@ -0,0 +1,524 @@
+//! Serves local resources from a single archive such as a zip file or a pak blob embedded in the binary.
+
+use bevy::asset::io::{AssetReader, AssetReaderError, AssetSource, AssetSourceId, PathStream, Reader, SliceReader, VecReader};
+use bevy::asset::AssetApp;
+use bevy::prelude::App;
+use bevy::tasks::futures_lite::stream;
+use bevy::utils::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const PAK_MAGIC: &[u8; 8] = b"FLURXPAK";
+const PAK_VERSION: u32 = 1;
+
+/// An in-memory archive of local resources.
+///
+/// The pack is served from the `flurx` protocol by registering it as an asset source
+/// via [`ResourcePackExt::register_resource_pack`] and specifying the source in
+/// [`WebviewWryPlugin::asset_source`](crate::prelude::WebviewWryPlugin::asset_source).
+///
+/// Gzip-compressed entries such as `index.js.gz` are served decompressed as `index.js`.
+#[derive(Clone, Debug)]
+pub struct ResourcePack(Arc<PackIndex>);
+
+#[derive(Debug)]
+struct PackIndex {
+    bytes: Cow<'static, [u8]>,
+    entries: HashMap<String, PackEntry>,
+    dirs: HashSet<String>,
+}
+
+#[derive(Debug, Clone)]
+struct PackEntry {
+    range: Range<usize>,
+    deflated: bool,
+}
+
+impl ResourcePack {
+    /// Loads the pak blob created by [`ResourcePackBuilder`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// // `include_bytes!("ui.pak").as_slice()` is also accepted.
+    /// let pack = ResourcePack::from_pak(std::fs::read("ui.pak").unwrap()).unwrap();
+    /// ```
+    pub fn from_pak(bytes: impl Into<Cow<'static, [u8]>>) -> Result<Self, ResourcePackError> {
+        let bytes = bytes.into();
+        let mut cursor = ByteCursor::new(&bytes, 0);
+        if cursor.take(PAK_MAGIC.len())? != PAK_MAGIC {
+            return Err(ResourcePackError::InvalidFormat("missing pak header"));
+        }
+        if cursor.u32()? != PAK_VERSION {
+            return Err(ResourcePackError::InvalidFormat("unsupported pak version"));
+        }
+        let mut entries = HashMap::new();
+        for _ in 0..cursor.u32()? {
+            let path_len = cursor.u32()? as usize;
+            let path = std::str::from_utf8(cursor.take(path_len)?)
+                .map_err(|_| ResourcePackError::InvalidFormat("path is not utf-8"))?
+                .to_string();
+            let offset = cursor.u64()? as usize;
+            let len = cursor.u64()? as usize;
+            if bytes.len() < offset.saturating_add(len) {
+                return Err(ResourcePackError::InvalidFormat("entry is out of bounds"));
+            }
+            entries.insert(path, PackEntry {
+                range: offset..offset + len,
+                deflated: false,
+            });
+        }
+        Ok(Self::new(bytes, entries))
+    }
+
+    /// Loads the zip archive.
+    ///
+    /// Only stored and deflated entries are supported, and ZIP64 is not supported.
+    pub fn from_zip(bytes: impl Into<Cow<'static, [u8]>>) -> Result<Self, ResourcePackError> {
+        const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+        const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+        const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+        const EOCD_LEN: usize = 22;
+
+        let bytes = bytes.into();
+        let search_start = bytes.len().saturating_sub(EOCD_LEN + u16::MAX as usize);
+        let eocd = (search_start..=bytes.len().saturating_sub(EOCD_LEN))
+            .rev()
+            .find(|&i| ByteCursor::new(&bytes, i).u32().is_ok_and(|signature| signature == EOCD_SIGNATURE))
+            .ok_or(ResourcePackError::InvalidFormat("missing end of central directory"))?;
+        let total_entries = ByteCursor::new(&bytes, eocd + 10).u16()?;
+        let central_offset = ByteCursor::new(&bytes, eocd + 16).u32()?;
+        if central_offset == u32::MAX {
+            return Err(ResourcePackError::InvalidFormat("ZIP64 is not supported"));
+        }
+
+        let mut entries = HashMap::new();
+        let mut cursor = ByteCursor::new(&bytes, central_offset as usize);
+        for _ in 0..total_entries {
+            let header = cursor.clone();
+            if cursor.u32()? != CENTRAL_SIGNATURE {
+                return Err(ResourcePackError::InvalidFormat("invalid central directory header"));
+            }
+            let method = header.at(10).u16()?;
+            let compressed_size = header.at(20).u32()?;
+            let name_len = header.at(28).u16()? as usize;
+            let extra_len = header.at(30).u16()? as usize;
+            let comment_len = header.at(32).u16()? as usize;
+            let local_offset = header.at(42).u32()?;
+            let name = std::str::from_utf8(header.at(46).take(name_len)?)
+                .map_err(|_| ResourcePackError::InvalidFormat("path is not utf-8"))?
+                .to_string();
+            cursor = header.at(46 + name_len + extra_len + comment_len);
+
+            if name.ends_with('/') {
+                continue;
+            }
+            if compressed_size == u32::MAX || local_offset == u32::MAX {
+                return Err(ResourcePackError::InvalidFormat("ZIP64 is not supported"));
+            }
+            let deflated = match method {
+                0 => false,
+                8 => true,
+                _ => return Err(ResourcePackError::UnsupportedCompression(name)),
+            };
+            let local = ByteCursor::new(&bytes, local_offset as usize);
+            if local.at(0).u32()? != LOCAL_SIGNATURE {
+                return Err(ResourcePackError::InvalidFormat("invalid local file header"));
+            }
+            let start = local_offset as usize + 30 + local.at(26).u16()? as usize + local.at(28).u16()? as usize;
+            let end = start + compressed_size as usize;
+            if bytes.len() < end {
+                return Err(ResourcePackError::InvalidFormat("entry is out of bounds"));
+            }
+            entries.insert(name, PackEntry {
+                range: start..end,
+                deflated,
+            });
+        }
+        Ok(Self::new(bytes, entries))
+    }
+
+    fn new(bytes: Cow<'static, [u8]>, entries: HashMap<String, PackEntry>) -> Self {
+        let dirs = entries
+            .keys()
+            .flat_map(|path| path.match_indices('/').map(|(i, _)| path[..i].to_string()))
+            .chain(std::iter::once(String::new()))
+            .collect();
+        Self(Arc::new(PackIndex {
+            bytes,
+            entries,
+            dirs,
+        }))
+    }
+
+    /// Returns the paths of all entries.
+    pub fn paths(&self) -> impl Iterator<Item=&str> {
+        self.0.entries.keys().map(String::as_str)
+    }
+
+    /// Returns the content of the entry at `path`.
+    pub fn get(&self, path: &str) -> Option<Cow<[u8]>> {
+        let entry = self.0.entries.get(path)?;
+        let bytes = &self.0.bytes[entry.range.clone()];
+        if entry.deflated {
+            let mut content = Vec::new();
+            flate2::read::DeflateDecoder::new(bytes).read_to_end(&mut content).ok()?;
+            Some(Cow::Owned(content))
+        } else {
+            Some(Cow::Borrowed(bytes))
+        }
+    }
+}
+
+impl AssetReader for ResourcePack {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let content = as_entry_path(path)
+            .and_then(|entry_path| self.get(&entry_path))
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+        let reader: Box<dyn Reader + 'a> = match content {
+            Cow::Borrowed(bytes) => Box::new(SliceReader::new(bytes)),
+            Cow::Owned(bytes) => Box::new(VecReader::new(bytes)),
+        };
+        Ok(reader)
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let mut meta_path = path.to_path_buf().into_os_string();
+        meta_path.push(".meta");
+        let meta_path = PathBuf::from(meta_path);
+        let content = as_entry_path(&meta_path)
+            .and_then(|entry_path| self.get(&entry_path))
+            .ok_or(AssetReaderError::NotFound(meta_path))?;
+        Ok(VecReader::new(content.into_owned()))
+    }
+
+    async fn read_directory<'a>(&'a self, path: &'a Path) -> Result<Box<PathStream>, AssetReaderError> {
+        let dir = as_entry_path(path).unwrap_or_default();
+        if !self.0.dirs.contains(&dir) {
+            return Err(AssetReaderError::NotFound(path.to_path_buf()));
+        }
+        let prefix = if dir.is_empty() { String::new() } else { format!("{dir}/") };
+        let children = self.0.entries
+            .keys()
+            .chain(self.0.dirs.iter())
+            .filter_map(|entry| {
+                let name = entry.strip_prefix(&prefix)?;
+                (!name.is_empty() && !name.contains('/')).then(|| path.join(name))
+            })
+            .collect::<Vec<_>>();
+        Ok(Box::new(stream::iter(children)))
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(as_entry_path(path).is_some_and(|dir| self.0.dirs.contains(&dir)))
+    }
+}
+
+/// Builds the pak blob loaded by [`ResourcePack::from_pak`].
+///
+/// This is typically used in `build.rs` or a packaging tool.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy_webview_wry::prelude::*;
+///
+/// ResourcePackBuilder::default()
+///     .add_dir("assets/ui")
+///     .unwrap()
+///     .gzip(true)
+///     .write("ui.pak")
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct ResourcePackBuilder {
+    entries: BTreeMap<String, Vec<u8>>,
+    gzip: bool,
+}
+
+impl ResourcePackBuilder {
+    /// Adds the entry at `path`.
+    pub fn add_file(mut self, path: impl Into<String>, content: Vec<u8>) -> Self {
+        self.entries.insert(path.into(), content);
+        self
+    }
+
+    /// Adds all files under `dir` recursively.
+    ///
+    /// The paths of the entries are relative to `dir`.
+    pub fn add_dir(mut self, dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dir = dir.as_ref();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if let Some(entry_path) = path.strip_prefix(dir).ok().and_then(as_entry_path) {
+                    self.entries.insert(entry_path, std::fs::read(&path)?);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Whether to replace each entry with the gzip-compressed `<path>.gz` entry to reduce the size of the pak.
+    ///
+    /// Entries which are already compressed such as images are kept as they are.
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Builds the pak blob.
+    pub fn build(mut self) -> std::io::Result<Vec<u8>> {
+        if self.gzip {
+            let mut compressed = Vec::new();
+            for (path, content) in self.entries.iter() {
+                let gz_path = format!("{path}.gz");
+                if !is_compressible(path) || self.entries.contains_key(&gz_path) {
+                    continue;
+                }
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(content)?;
+                compressed.push((path.clone(), gz_path, encoder.finish()?));
+            }
+            for (path, gz_path, content) in compressed {
+                self.entries.remove(&path);
+                self.entries.insert(gz_path, content);
+            }
+        }
+
+        let header_len = PAK_MAGIC.len() + 8 + self.entries
+            .keys()
+            .map(|path| 4 + path.len() + 16)
+            .sum::<usize>();
+        let mut pak = Vec::with_capacity(header_len + self.entries.values().map(Vec::len).sum::<usize>());
+        pak.extend_from_slice(PAK_MAGIC);
+        pak.extend_from_slice(&PAK_VERSION.to_le_bytes());
+        pak.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        let mut offset = header_len;
+        for (path, content) in self.entries.iter() {
+            pak.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            pak.extend_from_slice(path.as_bytes());
+            pak.extend_from_slice(&(offset as u64).to_le_bytes());
+            pak.extend_from_slice(&(content.len() as u64).to_le_bytes());
+            offset += content.len();
+        }
+        for content in self.entries.values() {
+            pak.extend_from_slice(content);
+        }
+        Ok(pak)
+    }
+
+    /// Builds the pak blob and writes it to `path`.
+    pub fn write(self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.build()?)
+    }
+}
+
+/// Registers [`ResourcePack`] as the asset source.
+pub trait ResourcePackExt {
+    /// Registers `pack` as the asset source `id`.
+    ///
+    /// Like [`AssetApp::register_asset_source`], this must be called before adding [`AssetPlugin`](bevy::prelude::AssetPlugin).
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::asset::io::AssetSourceId;
+    /// use bevy::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// let pack = ResourcePack::from_zip(std::fs::read("ui.zip").unwrap()).unwrap();
+    /// App::new()
+    ///     .register_resource_pack("ui_pack", pack)
+    ///     .add_plugins((
+    ///         DefaultPlugins,
+    ///         WebviewWryPlugin {
+    ///             local_root: LocalRoot::Assets("".into()),
+    ///             asset_source: AssetSourceId::from("ui_pack"),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// ```
+    fn register_resource_pack(&mut self, id: impl Into<AssetSourceId<'static>>, pack: ResourcePack) -> &mut Self;
+}
+
+impl ResourcePackExt for App {
+    fn register_resource_pack(&mut self, id: impl Into<AssetSourceId<'static>>, pack: ResourcePack) -> &mut Self {
+        self.register_asset_source(id, AssetSource::build().with_reader(move || Box::new(pack.clone())))
+    }
+}
+
+/// The error that occurs when loading [`ResourcePack`].
+#[derive(Debug)]
+pub enum ResourcePackError {
+    /// The archive is broken or not supported.
+    InvalidFormat(&'static str),
+
+    /// The entry is compressed with an unsupported method.
+    UnsupportedCompression(String),
+}
+
+impl Display for ResourcePackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFormat(reason) => write!(f, "invalid resource pack: {reason}"),
+            Self::UnsupportedCompression(path) => write!(f, "unsupported compression method: {path}"),
+        }
+    }
+}
+
+impl std::error::Error for ResourcePackError {}
+
+fn as_entry_path(path: &Path) -> Option<String> {
+    path
+        .components()
+        .map(|component| match component {
+            std::path::Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|segments| segments.join("/"))
+}
+
+fn is_compressible(path: &str) -> bool {
+    let extension = path.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase());
+    matches!(
+        extension.as_deref(),
+        Some("html" | "htm" | "css" | "js" | "mjs" | "cjs" | "json" | "map" | "svg" | "txt" | "xml" | "wasm" | "csv")
+    )
+}
+
+#[derive(Clone)]
+struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    const fn new(bytes: &'a [u8], position: usize) -> Self {
+        Self {
+            bytes,
+            position,
+        }
+    }
+
+    fn at(&self, offset: usize) -> Self {
+        Self::new(self.bytes, self.position + offset)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ResourcePackError> {
+        let end = self.position.saturating_add(len);
+        let bytes = self.bytes
+            .get(self.position..end)
+            .ok_or(ResourcePackError::InvalidFormat("unexpected end of data"))?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, ResourcePackError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, ResourcePackError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ResourcePackError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::resource_pack::{ResourcePack, ResourcePackBuilder};
+    use std::io::Write;
+
+    #[test]
+    fn read_built_pak() {
+        let pak = ResourcePackBuilder::default()
+            .add_file("index.html", b"<html></html>".to_vec())
+            .add_file("js/main.js", b"console.log(1)".to_vec())
+            .add_file("image.png", vec![0, 1, 2])
+            .gzip(true)
+            .build()
+            .unwrap();
+        let pack = ResourcePack::from_pak(pak).unwrap();
+        assert!(pack.get("index.html").is_none());
+        assert!(pack.get("index.html.gz").is_some());
+        assert!(pack.get("js/main.js.gz").is_some());
+        assert_eq!(pack.get("image.png").unwrap().as_ref(), [0, 1, 2]);
+        assert!(pack.get("image.png.gz").is_none());
+        assert!(pack.get("missing.html").is_none());
+        assert!(pack.0.dirs.contains("js"));
+    }
+
+    #[test]
+    fn reject_invalid_pak() {
+        assert!(ResourcePack::from_pak(b"NOTAPAK".as_slice()).is_err());
+    }
+
+    #[test]
+    fn read_zip() {
+        let deflated = {
+            let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(b"body { color: red; }").unwrap();
+            encoder.finish().unwrap()
+        };
+        let zip = zip_archive(&[
+            ("index.html", 0, b"<html></html>".to_vec()),
+            ("css/style.css", 8, deflated),
+        ]);
+        let pack = ResourcePack::from_zip(zip).unwrap();
+        assert_eq!(pack.get("index.html").unwrap().as_ref(), b"<html></html>");
+        assert_eq!(pack.get("css/style.css").unwrap().as_ref(), b"body { color: red; }");
+    }
+
+    fn zip_archive(entries: &[(&str, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut zip = Vec::new();
+        let mut central = Vec::new();
+        for (name, method, data) in entries {
+            let offset = zip.len() as u32;
+            zip.extend_from_slice(&0x0403_4b50_u32.to_le_bytes());
+            zip.extend_from_slice(&[20, 0, 0, 0]);
+            zip.extend_from_slice(&method.to_le_bytes());
+            zip.extend_from_slice(&[0; 8]);
+            zip.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            zip.extend_from_slice(&[0; 4]);
+            zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            zip.extend_from_slice(&[0; 2]);
+            zip.extend_from_slice(name.as_bytes());
+            zip.extend_from_slice(data);
+
+            central.extend_from_slice(&0x0201_4b50_u32.to_le_bytes());
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            central.extend_from_slice(&method.to_le_bytes());
+            central.extend_from_slice(&[0; 8]);
+            central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            central.extend_from_slice(&[0; 4]);
+            central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central.extend_from_slice(&[0; 12]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let central_offset = zip.len() as u32;
+        zip.extend_from_slice(&central);
+        zip.extend_from_slice(&0x0605_4b50_u32.to_le_bytes());
+        zip.extend_from_slice(&[0; 4]);
+        zip.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&central_offset.to_le_bytes());
+        zip.extend_from_slice(&[0; 2]);
+        zip
+    }
+}
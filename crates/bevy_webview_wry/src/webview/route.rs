@@ -0,0 +1,231 @@
+//! Provides REST-style routes served from the `flurx` protocol.
+
+use crate::prelude::{IntoProtocolResponse, ProtocolRequest, ProtocolResponse};
+use crate::webview::protocol_access::percent_decode;
+use bevy::prelude::{App, Entity, In, IntoSystem, Resource};
+use bevy::utils::HashMap;
+use bevy_flurx::prelude::{once, ActionSeed, Map};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+pub use wry::http::Method;
+
+/// The request passed to the route handler.
+pub struct RouteRequest {
+    /// The entity associated with the webview that sent the request.
+    pub webview_entity: Entity,
+
+    /// The percent-decoded path parameters captured by the route pattern such as `:id`.
+    pub params: HashMap<String, String>,
+
+    /// The raw request.
+    pub request: ProtocolRequest,
+}
+
+impl RouteRequest {
+    /// Returns the path parameter associated with `name`.
+    #[inline]
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Deserializes the request body as json.
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(self.request.body())
+    }
+}
+
+type BoxedRouteHandler = Box<dyn Fn() -> ActionSeed<RouteRequest, ProtocolResponse> + Send + Sync>;
+
+/// The handler of the route registered by [`WebviewRouteExt::add_webview_route`].
+pub struct RouteHandler(BoxedRouteHandler);
+
+impl RouteHandler {
+    /// Creates the handler that runs `system` every time a request is received.
+    ///
+    /// The output of the system is converted into the response via [`IntoProtocolResponse`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// RouteHandler::system(|In(_request): In<RouteRequest>, names: Query<&Name>| {
+    ///     Json(names.iter().map(|name| name.to_string()).collect::<Vec<_>>())
+    /// });
+    /// ```
+    pub fn system<Sys, Out, M>(system: Sys) -> Self
+    where
+        Sys: IntoSystem<In<RouteRequest>, Out, M> + Clone + Send + Sync + 'static,
+        Out: IntoProtocolResponse + Send + Sync + 'static,
+    {
+        Self(Box::new(move || {
+            once::run(system.clone()).map(IntoProtocolResponse::into_response)
+        }))
+    }
+
+    /// Creates the handler that runs the action created by `f` every time a request is received.
+    pub fn action<A, Out>(f: impl Fn() -> A + Send + Sync + 'static) -> Self
+    where
+        A: Into<ActionSeed<RouteRequest, Out>>,
+        Out: IntoProtocolResponse + Send + Sync + 'static,
+    {
+        Self(Box::new(move || f().into().map(IntoProtocolResponse::into_response)))
+    }
+
+    #[inline]
+    pub(crate) fn seed(&self) -> ActionSeed<RouteRequest, ProtocolResponse> {
+        (self.0)()
+    }
+}
+
+/// Registers the routes served from the `flurx` protocol.
+pub trait WebviewRouteExt {
+    /// Registers the route which matches `method` and the path `pattern`.
+    ///
+    /// The segment starting with `:` captures the path parameter,
+    /// and the last segment `*` matches all remaining segments.
+    ///
+    /// Routes take precedence over local resources,
+    /// so unmatched paths fall through to files in the local root.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_webview_wry::prelude::*;
+    ///
+    /// // fetch("flurx://localhost/api/players/3")
+    /// App::new()
+    ///     .add_plugins((DefaultPlugins, WebviewWryPlugin::default()))
+    ///     .add_webview_route(Method::GET, "/api/players/:id", RouteHandler::system(|In(request): In<RouteRequest>| {
+    ///         format!("player {}", request.param("id").unwrap())
+    ///     }));
+    /// ```
+    fn add_webview_route(&mut self, method: Method, pattern: &str, handler: RouteHandler) -> &mut Self;
+}
+
+impl WebviewRouteExt for App {
+    fn add_webview_route(&mut self, method: Method, pattern: &str, handler: RouteHandler) -> &mut Self {
+        self
+            .world_mut()
+            .get_resource_or_insert_with::<WebviewRoutes>(WebviewRoutes::default)
+            .0
+            .push(Arc::new(Route {
+                method,
+                pattern: RoutePattern::new(pattern),
+                handler,
+            }));
+        self
+    }
+}
+
+#[derive(Resource, Default, Clone)]
+pub(crate) struct WebviewRoutes(Vec<Arc<Route>>);
+
+impl WebviewRoutes {
+    pub(crate) fn find(&self, method: &Method, path: &str) -> Option<(&RouteHandler, HashMap<String, String>)> {
+        self.0.iter().find_map(|route| {
+            if route.method != *method {
+                return None;
+            }
+            route
+                .pattern
+                .matches(path)
+                .map(|params| (&route.handler, params))
+        })
+    }
+}
+
+pub(crate) struct Route {
+    method: Method,
+    pattern: RoutePattern,
+    handler: RouteHandler,
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    Wildcard,
+}
+
+#[derive(Debug, PartialEq)]
+struct RoutePattern(Vec<Segment>);
+
+impl RoutePattern {
+    fn new(pattern: &str) -> Self {
+        Self(split_segments(pattern)
+            .map(|segment| {
+                if segment == "*" {
+                    Segment::Wildcard
+                } else if let Some(name) = segment.strip_prefix(':') {
+                    Segment::Param(name.to_string())
+                } else {
+                    Segment::Static(segment.to_string())
+                }
+            })
+            .collect())
+    }
+
+    fn matches(&self, path: &str) -> Option<HashMap<String, String>> {
+        let mut params = HashMap::new();
+        let mut segments = split_segments(path);
+        for pattern in self.0.iter() {
+            match pattern {
+                Segment::Wildcard => return Some(params),
+                Segment::Static(s) => {
+                    if segments.next()? != s {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    params.insert(name.clone(), percent_decode(segments.next()?));
+                }
+            }
+        }
+        segments.next().is_none().then_some(params)
+    }
+}
+
+fn split_segments(path: &str) -> impl Iterator<Item=&str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::route::RoutePattern;
+
+    #[test]
+    fn match_static_path() {
+        let pattern = RoutePattern::new("/api/players");
+        assert!(pattern.matches("/api/players").is_some());
+        assert!(pattern.matches("/api/players/").is_some());
+        assert!(pattern.matches("/api/player").is_none());
+        assert!(pattern.matches("/api/players/1").is_none());
+    }
+
+    #[test]
+    fn capture_params() {
+        let pattern = RoutePattern::new("/api/players/:id/items/:item");
+        let params = pattern.matches("/api/players/3/items/sword").unwrap();
+        assert_eq!(params.get("id").map(String::as_str), Some("3"));
+        assert_eq!(params.get("item").map(String::as_str), Some("sword"));
+        assert!(pattern.matches("/api/players/3/items").is_none());
+    }
+
+    #[test]
+    fn decode_params() {
+        let pattern = RoutePattern::new("/api/players/:name");
+        let params = pattern.matches("/api/players/John%20Doe").unwrap();
+        assert_eq!(params.get("name").map(String::as_str), Some("John Doe"));
+    }
+
+    #[test]
+    fn match_wildcard() {
+        let pattern = RoutePattern::new("/api/*");
+        assert!(pattern.matches("/api/a/b/c").is_some());
+        assert!(pattern.matches("/api").is_some());
+        assert!(pattern.matches("/index.html").is_none());
+    }
+}
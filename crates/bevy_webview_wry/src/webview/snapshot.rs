@@ -0,0 +1,259 @@
+//! Captures the rendered contents of webviews into [`Image`].
+
+use crate::prelude::WryWebViews;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::Image;
+use bevy::prelude::{App, Entity, In, NonSend, Plugin, Res, Resource};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::utils::HashMap;
+use bevy_flurx::action::{once, wait, Action};
+use bevy_flurx::prelude::Pipe;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The result of [`snapshot`].
+pub type SnapshotResult<T = Image> = Result<T, SnapshotError>;
+
+/// Captures the visible area of the webview into [`Image`].
+///
+/// The image is in [`TextureFormat::Rgba8UnormSrgb`] and its size is the size of the webview in physical pixels.
+/// Transparent areas of the page stay transparent.
+/// To encode it as PNG, convert it with [`Image::try_into_dynamic`].
+///
+/// The webview must be shown; it works under a virtual display such as Xvfb,
+/// so snapshots can be compared in CI.
+///
+/// Currently, this is supported only on Linux; the other platforms return [`SnapshotError::Unsupported`].
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_webview_wry::prelude::*;
+///
+/// fn capture(mut commands: Commands, webview: Query<Entity, With<Webview>>) {
+///     let entity = webview.single();
+///     commands.spawn(Reactor::schedule(move |task| async move {
+///         match task.will(Update, snapshot(entity)).await {
+///             Ok(image) => info!("captured {:?}", image.size()),
+///             Err(e) => error!("{e}"),
+///         }
+///     }));
+/// }
+/// ```
+pub fn snapshot(entity: Entity) -> Action<Entity, SnapshotResult> {
+    once::run(request_snapshot)
+        .with(entity)
+        .pipe(wait::output(wait_snapshot))
+}
+
+/// The error that occurs in [`snapshot`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SnapshotError {
+    /// The webview associated with the entity does not exist.
+    WebviewNotFound(Entity),
+
+    /// Snapshots are not supported on this platform.
+    Unsupported,
+
+    /// The webview failed to render the snapshot.
+    Failed(String),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WebviewNotFound(entity) => write!(f, "webview not found: {entity}"),
+            Self::Unsupported => f.write_str("snapshots are not supported on this platform"),
+            Self::Failed(e) => write!(f, "failed to take a snapshot: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+pub(crate) struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Snapshots>();
+    }
+}
+
+/// The snapshots being rendered, keyed by the request id.
+#[derive(Resource, Clone, Default)]
+struct Snapshots {
+    next_id: Arc<AtomicU64>,
+    finished: Arc<Mutex<HashMap<u64, SnapshotResult>>>,
+}
+
+fn request_snapshot(
+    In(entity): In<Entity>,
+    web_views: NonSend<WryWebViews>,
+    snapshots: Res<Snapshots>,
+) -> SnapshotResult<u64> {
+    let webview = web_views.get(&entity).ok_or(SnapshotError::WebviewNotFound(entity))?;
+    let id = snapshots.next_id.fetch_add(1, Ordering::Relaxed);
+    render_snapshot(webview, id, snapshots.clone())?;
+    Ok(id)
+}
+
+fn wait_snapshot(
+    In(request): In<SnapshotResult<u64>>,
+    snapshots: Res<Snapshots>,
+) -> Option<SnapshotResult> {
+    match request {
+        Ok(id) => snapshots.finished.lock().unwrap().remove(&id),
+        Err(e) => Some(Err(e)),
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn render_snapshot(
+    webview: &wry::WebView,
+    id: u64,
+    snapshots: Snapshots,
+) -> SnapshotResult<()> {
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+    use wry::WebViewExtUnix;
+
+    webview.webview().snapshot(
+        SnapshotRegion::Visible,
+        SnapshotOptions::TRANSPARENT_BACKGROUND,
+        None::<&webkit2gtk::gio::Cancellable>,
+        move |surface| {
+            let image = surface
+                .map_err(|e| SnapshotError::Failed(e.to_string()))
+                .and_then(surface_to_image);
+            snapshots.finished.lock().unwrap().insert(id, image);
+        },
+    );
+    Ok(())
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn render_snapshot(
+    _: &wry::WebView,
+    _: u64,
+    _: Snapshots,
+) -> SnapshotResult<()> {
+    Err(SnapshotError::Unsupported)
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn surface_to_image(surface: gtk::cairo::Surface) -> SnapshotResult {
+    use gtk::cairo::{Format, ImageSurface};
+
+    let mut surface = ImageSurface::try_from(surface)
+        .map_err(|_| SnapshotError::Failed("the snapshot is not an image surface".to_string()))?;
+    let opaque = match surface.format() {
+        Format::ARgb32 => false,
+        Format::Rgb24 => true,
+        format => return Err(SnapshotError::Failed(format!("unsupported pixel format: {format:?}"))),
+    };
+    surface.flush();
+    let width = surface.width() as u32;
+    let height = surface.height() as u32;
+    let stride = surface.stride() as usize;
+    let data = surface.data().map_err(|e| SnapshotError::Failed(e.to_string()))?;
+    let rgba = argb32_to_rgba(&data, width as usize, height as usize, stride, opaque);
+    Ok(Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        rgba,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// Converts the premultiplied native-endian ARGB32 pixels of cairo into straight RGBA8.
+#[cfg_attr(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)), allow(dead_code))]
+fn argb32_to_rgba(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    opaque: bool,
+) -> Vec<u8> {
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(stride).take(height) {
+        for pixel in row[..width * 4].chunks_exact(4) {
+            let pixel = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+            let a = if opaque { 255 } else { pixel >> 24 };
+            let unpremultiply = |c: u32| match a {
+                0 => 0,
+                a => ((c * 255 + a / 2) / a).min(255) as u8,
+            };
+            rgba.extend([
+                unpremultiply((pixel >> 16) & 0xFF),
+                unpremultiply((pixel >> 8) & 0xFF),
+                unpremultiply(pixel & 0xFF),
+                a as u8,
+            ]);
+        }
+    }
+    rgba
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::snapshot::argb32_to_rgba;
+
+    fn argb(a: u32, r: u32, g: u32, b: u32) -> [u8; 4] {
+        ((a << 24) | (r << 16) | (g << 8) | b).to_ne_bytes()
+    }
+
+    #[test]
+    fn convert_opaque_pixels() {
+        let data = [argb(255, 10, 20, 30), argb(255, 40, 50, 60)].concat();
+        assert_eq!(argb32_to_rgba(&data, 2, 1, 8, false), vec![10, 20, 30, 255, 40, 50, 60, 255]);
+    }
+
+    #[test]
+    fn unpremultiply_alpha() {
+        let data = [argb(128, 64, 0, 128), argb(0, 0, 0, 0)].concat();
+        assert_eq!(argb32_to_rgba(&data, 2, 1, 8, false), vec![128, 0, 255, 128, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn ignore_alpha_of_rgb24() {
+        let data = argb(0, 1, 2, 3);
+        assert_eq!(argb32_to_rgba(&data, 1, 1, 4, true), vec![1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn skip_row_padding() {
+        let data = [argb(255, 1, 1, 1), [9; 4], argb(255, 2, 2, 2), [9; 4]].concat();
+        assert_eq!(argb32_to_rgba(&data, 1, 2, 8, false), vec![1, 1, 1, 255, 2, 2, 2, 255]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Follows [`Theme`] and the theme of the window in webviews.
+//!
+//! The resolved theme is exposed to pages as `window.__FLURX__.theme`, which is `"light"`, `"dark"` or `null` if unknown,
+//! and its changes are notified by the `flurx:themechange` event dispatched on `window`.
+//!
+//! ```js
+//! window.addEventListener("flurx:themechange", (e) => {
+//!     document.documentElement.dataset.theme = e.detail.theme;
+//! });
+//! ```
+
+use crate::prelude::WryWebViews;
+use crate::util::WryResultLog;
+use bevy::prelude::{App, Entity, EventReader, NonSend, Plugin, PreUpdate, Query, Res, ResMut, Resource, Update, Window, With};
+use bevy::utils::HashMap;
+use bevy::window::{WindowTheme, WindowThemeChanged};
+use bevy_flurx_ipc::ipc_events::IpcEventExt;
+use bevy_flurx_ipc::prelude::IpcEvent;
+use bevy_webview_core::prelude::{EmbedWithin, Theme, WebviewInitialized};
+use serde::Deserialize;
+
+pub(crate) struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SystemThemes>()
+            .init_resource::<AppliedThemes>()
+            .add_ipc_event::<ThemeRequest>("FLURX|theme")
+            .add_systems(PreUpdate, (
+                track_system_themes,
+                resend_requested_themes,
+            ))
+            .add_systems(Update, apply_themes);
+        app
+            .world_mut()
+            .get_resource_or_insert_with(crate::webview::load_webview::WryInitializationScripts::default)
+            .0
+            .push(include_str!("../../scripts/theme.js").to_string());
+    }
+}
+
+/// The system theme reported by [`WindowThemeChanged`] for each window.
+///
+/// The system theme of a window is unknown until it changes for the first time.
+#[derive(Resource, Default)]
+struct SystemThemes(HashMap<Entity, WindowTheme>);
+
+/// The theme last sent to each webview.
+#[derive(Resource, Default)]
+struct AppliedThemes(HashMap<Entity, Option<WindowTheme>>);
+
+/// Requested by the page when the script is loaded.
+#[derive(Deserialize)]
+struct ThemeRequest {}
+
+fn track_system_themes(
+    mut er: EventReader<WindowThemeChanged>,
+    mut system_themes: ResMut<SystemThemes>,
+) {
+    for WindowThemeChanged { window, theme } in er.read() {
+        system_themes.0.insert(*window, *theme);
+    }
+}
+
+fn resend_requested_themes(
+    mut er: EventReader<IpcEvent<ThemeRequest>>,
+    mut applied: ResMut<AppliedThemes>,
+) {
+    for IpcEvent { webview_entity, .. } in er.read() {
+        applied.0.remove(webview_entity);
+    }
+}
+
+fn apply_themes(
+    mut applied: ResMut<AppliedThemes>,
+    web_views: NonSend<WryWebViews>,
+    system_themes: Res<SystemThemes>,
+    views: Query<(Entity, &Theme, Option<&EmbedWithin>), With<WebviewInitialized>>,
+    windows: Query<&Window>,
+) {
+    applied.0.retain(|entity, _| web_views.0.contains_key(entity));
+    for (webview_entity, theme, embed_within) in views.iter() {
+        let window_entity = embed_within.map_or(webview_entity, |embed_within| embed_within.0);
+        let theme = resolve_theme(theme, windows.get(window_entity).ok(), || {
+            system_themes.0.get(&window_entity).copied()
+        });
+        if applied.0.get(&webview_entity).is_some_and(|applied| *applied == theme) {
+            continue;
+        }
+        let Some(webview) = web_views.0.get(&webview_entity) else {
+            continue;
+        };
+        webview
+            .evaluate_script(&format!("window.__FLURX__.__setTheme({});", theme_name(theme)))
+            .output_log_if_failed();
+        #[cfg(target_os = "windows")]
+        {
+            use wry::WebViewExtWindows;
+            webview
+                .set_theme(match theme {
+                    Some(WindowTheme::Light) => wry::Theme::Light,
+                    Some(WindowTheme::Dark) => wry::Theme::Dark,
+                    None => wry::Theme::Auto,
+                })
+                .output_log_if_failed();
+        }
+        applied.0.insert(webview_entity, theme);
+    }
+}
+
+/// Resolves the theme of the webview.
+///
+/// [`Theme::Auto`] follows [`Window::window_theme`] if it is set, otherwise the system theme.
+fn resolve_theme(
+    theme: &Theme,
+    window: Option<&Window>,
+    system_theme: impl FnOnce() -> Option<WindowTheme>,
+) -> Option<WindowTheme> {
+    match theme {
+        Theme::Light => Some(WindowTheme::Light),
+        Theme::Dark => Some(WindowTheme::Dark),
+        Theme::Auto => window
+            .and_then(|window| window.window_theme)
+            .or_else(system_theme),
+    }
+}
+
+fn theme_name(theme: Option<WindowTheme>) -> &'static str {
+    match theme {
+        Some(WindowTheme::Light) => "\"light\"",
+        Some(WindowTheme::Dark) => "\"dark\"",
+        None => "null",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::theme::resolve_theme;
+    use bevy::window::{Window, WindowTheme};
+    use bevy_webview_core::prelude::Theme;
+
+    #[test]
+    fn explicit_theme_wins() {
+        let window = Window {
+            window_theme: Some(WindowTheme::Light),
+            ..Default::default()
+        };
+        assert_eq!(resolve_theme(&Theme::Dark, Some(&window), || Some(WindowTheme::Light)), Some(WindowTheme::Dark));
+    }
+
+    #[test]
+    fn auto_follows_window_theme() {
+        let window = Window {
+            window_theme: Some(WindowTheme::Dark),
+            ..Default::default()
+        };
+        assert_eq!(resolve_theme(&Theme::Auto, Some(&window), || Some(WindowTheme::Light)), Some(WindowTheme::Dark));
+    }
+
+    #[test]
+    fn auto_falls_back_to_system_theme() {
+        assert_eq!(resolve_theme(&Theme::Auto, Some(&Window::default()), || Some(WindowTheme::Light)), Some(WindowTheme::Light));
+        assert_eq!(resolve_theme(&Theme::Auto, None, || None), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Applies [`WebviewZoom`] and follows the zoom changed by the user.
+
+use crate::prelude::WryWebViews;
+use crate::util::WryResultLog;
+use crate::webview::handlers::{RegisterWryEvent, WryEvents};
+use bevy::prelude::{App, Entity, Event, EventReader, EventWriter, NonSend, Plugin, PreUpdate, Query, Reflect, Res, ResMut, Resource, Update, Window, With};
+use bevy::utils::HashMap;
+use bevy_flurx_ipc::ipc_events::IpcEventExt;
+use bevy_flurx_ipc::prelude::IpcEvent;
+use bevy_webview_core::prelude::{EmbedWithin, WebviewInitialized, WebviewZoom};
+use serde::Deserialize;
+
+/// Fired when the user changed the zoom of the webview with hotkeys or gestures.
+///
+/// [`WebviewZoom`] has already been updated when this event is fired.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Reflect)]
+pub struct WebviewZoomChanged {
+    /// The entity associated with the webview from which this event was fired.
+    pub webview_entity: Entity,
+
+    /// The new zoom.
+    pub zoom: f64,
+}
+
+/// The zoom reported from the webview.
+pub(crate) enum ReportedZoom {
+    /// The zoom level of the webview.
+    Zoom(f64),
+
+    /// `window.devicePixelRatio`, which is the product of the scale factor of the monitor and the zoom.
+    DevicePixelRatio(f64),
+}
+
+pub(crate) struct ReportedZoomEvent {
+    pub webview_entity: Entity,
+    pub zoom: ReportedZoom,
+}
+
+/// The zoom last applied to each webview, including the scale of the window.
+#[derive(Resource, Default)]
+struct AppliedZooms(HashMap<Entity, f64>);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZoomPayload {
+    device_pixel_ratio: f64,
+}
+
+pub(crate) struct ZoomPlugin;
+
+impl Plugin for ZoomPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_wry_event::<WebviewZoomChanged>()
+            .init_resource::<WryEvents<ReportedZoomEvent>>()
+            .init_resource::<AppliedZooms>()
+            .add_ipc_event::<ZoomPayload>("FLURX|zoom")
+            .add_systems(PreUpdate, (
+                report_device_pixel_ratio,
+                sync_reported_zoom,
+            ))
+            .add_systems(Update, apply_zoom);
+
+        // WebKitGTK does not reflect the zoom in `devicePixelRatio`; the zoom level is watched by the signal instead.
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )))]
+        app
+            .world_mut()
+            .get_resource_or_insert_with(crate::webview::load_webview::WryInitializationScripts::default)
+            .0
+            .push(include_str!("../../scripts/zoom.js").to_string());
+    }
+}
+
+fn report_device_pixel_ratio(
+    mut er: EventReader<IpcEvent<ZoomPayload>>,
+    reported: Res<WryEvents<ReportedZoomEvent>>,
+) {
+    for IpcEvent { webview_entity, payload } in er.read() {
+        reported.push(ReportedZoomEvent {
+            webview_entity: *webview_entity,
+            zoom: ReportedZoom::DevicePixelRatio(payload.device_pixel_ratio),
+        });
+    }
+}
+
+fn sync_reported_zoom(
+    mut ew: EventWriter<WebviewZoomChanged>,
+    mut applied: ResMut<AppliedZooms>,
+    mut zooms: Query<(&mut WebviewZoom, Option<&EmbedWithin>)>,
+    reported: Res<WryEvents<ReportedZoomEvent>>,
+    windows: Query<&Window>,
+) {
+    for ReportedZoomEvent { webview_entity, zoom } in reported.take_events() {
+        let Ok((mut webview_zoom, embed_within)) = zooms.get_mut(webview_entity) else {
+            continue;
+        };
+        let Ok(window) = windows.get(embed_within.map_or(webview_entity, |embed_within| embed_within.0)) else {
+            continue;
+        };
+        let zoom = resolve_reported_zoom(zoom, window);
+        if applied.0.get(&webview_entity).is_some_and(|applied| approx_eq(*applied, zoom)) {
+            continue;
+        }
+        applied.0.insert(webview_entity, zoom);
+        webview_zoom.0 = zoom / window_scale(window);
+        ew.send(WebviewZoomChanged {
+            webview_entity,
+            zoom: webview_zoom.0,
+        });
+    }
+}
+
+fn apply_zoom(
+    mut applied: ResMut<AppliedZooms>,
+    web_views: NonSend<WryWebViews>,
+    views: Query<(Entity, &WebviewZoom, Option<&EmbedWithin>), With<WebviewInitialized>>,
+    windows: Query<&Window>,
+) {
+    applied.0.retain(|entity, _| web_views.0.contains_key(entity));
+    for (webview_entity, zoom, embed_within) in views.iter() {
+        let Ok(window) = windows.get(embed_within.map_or(webview_entity, |embed_within| embed_within.0)) else {
+            continue;
+        };
+        let zoom = zoom.0 * window_scale(window);
+        if applied.0.get(&webview_entity).is_some_and(|applied| approx_eq(*applied, zoom)) {
+            continue;
+        }
+        let Some(webview) = web_views.0.get(&webview_entity) else {
+            continue;
+        };
+        webview.zoom(zoom).output_log_if_failed();
+        applied.0.insert(webview_entity, zoom);
+    }
+}
+
+/// Returns the zoom of the webview including the scale of the window.
+fn resolve_reported_zoom(zoom: ReportedZoom, window: &Window) -> f64 {
+    match zoom {
+        ReportedZoom::Zoom(zoom) => zoom,
+        ReportedZoom::DevicePixelRatio(ratio) => ratio / window.resolution.base_scale_factor() as f64,
+    }
+}
+
+/// Returns the ratio of the scale factor of the window to the scale factor of the monitor.
+fn window_scale(window: &Window) -> f64 {
+    (window.scale_factor() / window.resolution.base_scale_factor()) as f64
+}
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::webview::zoom::{resolve_reported_zoom, window_scale, ReportedZoom};
+    use bevy::window::Window;
+
+    fn window(base_scale_factor: f32, scale_factor_override: Option<f32>) -> Window {
+        let mut window = Window::default();
+        window.resolution.set_scale_factor(base_scale_factor);
+        window.resolution.set_scale_factor_override(scale_factor_override);
+        window
+    }
+
+    #[test]
+    fn zoom_level_is_used_as_is() {
+        let window = window(2.0, None);
+        assert_eq!(resolve_reported_zoom(ReportedZoom::Zoom(1.5), &window), 1.5);
+    }
+
+    #[test]
+    fn device_pixel_ratio_is_divided_by_monitor_scale_factor() {
+        let window = window(2.0, None);
+        assert_eq!(resolve_reported_zoom(ReportedZoom::DevicePixelRatio(3.0), &window), 1.5);
+    }
+
+    #[test]
+    fn window_scale_is_excluded_from_webview_zoom() {
+        let window = window(2.0, Some(3.0));
+        let zoom = resolve_reported_zoom(ReportedZoom::DevicePixelRatio(3.0), &window);
+        assert_eq!(window_scale(&window), 1.5);
+        assert_eq!(zoom / window_scale(&window), 1.0);
+    }
+}
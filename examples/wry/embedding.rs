@@ -24,7 +24,8 @@ fn main() {
                 ..default()
             }),
             WebviewWryPlugin {
-                local_root: PathBuf::from("ui").join("embedding")
+                local_root: LocalRoot::Assets(PathBuf::from("ui").join("embedding")),
+                ..default()
             }
         ))
         .add_systems(Startup, spawn_webview)
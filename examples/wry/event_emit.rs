@@ -13,7 +13,8 @@ fn main() {
             DefaultPlugins,
             AllWebWindowPlugins,
             WebviewWryPlugin {
-                local_root: PathBuf::from("ui").join("event_emit")
+                local_root: LocalRoot::Assets(PathBuf::from("ui").join("event_emit")),
+                ..default()
             }
         ))
         .insert_resource(CountTimer(Timer::new(Duration::from_secs(1), TimerMode::Repeating)))
@@ -14,7 +14,8 @@ fn main() {
         .add_plugins((
             DefaultPlugins,
             WebviewWryPlugin {
-                local_root: PathBuf::from("ui").join("event_listen")
+                local_root: LocalRoot::Assets(PathBuf::from("ui").join("event_listen")),
+                ..default()
             }
         ))
         .add_ipc_event::<MessageFromWebview>("message")
@@ -14,7 +14,8 @@ fn main() {
         .add_plugins((
             DefaultPlugins,
             WebviewWryPlugin {
-                local_root: PathBuf::from("ui").join("ipc_command")
+                local_root: LocalRoot::Assets(PathBuf::from("ui").join("ipc_command")),
+                ..default()
             }
         ))
         .add_systems(Startup, spawn_webview)
@@ -0,0 +1,68 @@
+import {invoke} from "./core";
+
+export interface Cookie {
+    name: string,
+    value: string,
+    domain?: string | null,
+    path?: string | null,
+    secure?: boolean,
+    httpOnly?: boolean,
+    sameSite?: "Strict" | "Lax" | "None" | null,
+    /**
+     * The expiration time in seconds since the unix epoch.
+     */
+    expires?: number | null,
+}
+
+export namespace browsingData {
+    /**
+     * Gets the cookies that would be sent to the current page.
+     *
+     * The values of `HttpOnly` cookies are empty.
+     *
+     * @example
+     * import {browsingData} from "bevy_flurx_api";
+     * const cookies: Cookie[] = await browsingData.cookies();
+     */
+    export const cookies = async (): Promise<Cookie[]> => {
+        return await invoke("FLURX|browsing_data::cookies");
+    }
+
+    /**
+     * Sets the cookie for the current page.
+     *
+     * `HttpOnly` cookies can't be set.
+     * The assignment is not verified, so the cookie may be ignored by the webview, for example, if the domain doesn't match.
+     *
+     * @example
+     * import {browsingData} from "bevy_flurx_api";
+     * await browsingData.setCookie({name: "lang", value: "en", path: "/"});
+     */
+    export const setCookie = async (cookie: Cookie): Promise<void> => {
+        await invoke("FLURX|browsing_data::set_cookie", cookie);
+    }
+
+    /**
+     * Deletes the cookie matching the name, the domain and the path from the current page.
+     *
+     * `HttpOnly` cookies can't be deleted; use {@link clear} instead.
+     *
+     * @example
+     * import {browsingData} from "bevy_flurx_api";
+     * await browsingData.deleteCookie({name: "lang", value: "", path: "/"});
+     */
+    export const deleteCookie = async (cookie: Cookie): Promise<void> => {
+        await invoke("FLURX|browsing_data::delete_cookie", cookie);
+    }
+
+    /**
+     * Clears all browsing data of the webview, including cookies, caches and local storage.
+     *
+     * @example
+     * import {browsingData} from "bevy_flurx_api";
+     * await browsingData.clear();
+     */
+    export const clear = async (): Promise<void> => {
+        await invoke("FLURX|browsing_data::clear");
+    }
+}
@@ -8,9 +8,11 @@ export * from "./notification";
 export * from "./os";
 export * from "./monitor";
 export * from "./http";
+export * from "./browsingData";
 export * from "./core";
 export {PhysicalPosition, PhysicalSize} from "./core";
 export {Monitor} from "./monitor";
+export {Cookie} from "./browsingData";
 export {WebWindow} from "./webWindow";
 
 
@@ -11,6 +11,7 @@ import {
     os,
     path,
     http,
+    browsingData,
     WebWindow
 } from "../index";
 
@@ -23,6 +24,8 @@ declare global {
         },
         __FLURX__: {
             windowIdentifier: string,
+            cspNonce?: string,
+            theme: "light" | "dark" | null,
             app: typeof app,
             fs: typeof fs,
             path: typeof path,
@@ -31,6 +34,7 @@ declare global {
             dialog: typeof dialog,
             notification: typeof notification,
             http: typeof http,
+            browsingData: typeof browsingData,
             os: typeof os,
             monitor: typeof monitor,
             WebWindow: typeof WebWindow,